@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy::render::camera::Viewport;
 use serde::{Deserialize, Serialize};
 
 /// The camera will be a child entity of the camera frame.
@@ -38,3 +39,87 @@ impl Default for CameraFrame {
 /// The camera will follow the entity with this component.
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Component)]
 pub struct FocalPoint;
+
+/// Decides how much of the world the camera shows, whatever the size of the window.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Component, PartialEq)]
+pub enum CameraFit {
+    /// Show this many rows of tiles. The number of columns depends on the shape of the window.
+    Rows(f32),
+    /// Show exactly an area of this width and height, centered on the camera. If the window has a
+    /// different shape, the area is letterboxed: the rest of the window is left empty.
+    Area(Vec2),
+}
+
+impl Default for CameraFit {
+    fn default() -> Self {
+        CameraFit::Rows(24.)
+    }
+}
+
+impl CameraFit {
+    /// Returns the viewport and the scale the camera needs to fit in a window of the given
+    /// physical size, or None if the window has no size (for example, if it is minimised).
+    #[must_use]
+    pub fn fit(&self, window: UVec2, scale_factor: f32) -> Option<(Option<Viewport>, f32)> {
+        if window.x == 0 || window.y == 0 {
+            return None;
+        }
+        let window_size = window.as_vec2();
+        match *self {
+            CameraFit::Rows(rows) => Some((None, rows * scale_factor / window_size.y)),
+            CameraFit::Area(area) => {
+                let pixels_per_tile = (window_size.x / area.x).min(window_size.y / area.y);
+                let size = (area * pixels_per_tile)
+                    .round()
+                    .as_uvec2()
+                    .min(window)
+                    .max(UVec2::ONE);
+                let viewport = Viewport {
+                    physical_position: (window - size) / 2,
+                    physical_size: size,
+                    ..default()
+                };
+                Some((Some(viewport), scale_factor / pixels_per_tile))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_fill_the_window() {
+        let (viewport, scale) = CameraFit::Rows(24.)
+            .fit(UVec2::new(1920, 1080), 1.)
+            .unwrap();
+        assert!(viewport.is_none());
+        assert_eq!(scale * 1080., 24.);
+        let (_, scale) = CameraFit::Rows(24.)
+            .fit(UVec2::new(1920, 1080), 2.)
+            .unwrap();
+        assert_eq!(scale * 540., 24.);
+    }
+
+    #[test]
+    fn area_is_letterboxed() {
+        let fit = CameraFit::Area(Vec2::new(40., 20.));
+        let (viewport, scale) = fit.fit(UVec2::new(1000, 1000), 1.).unwrap();
+        let viewport = viewport.unwrap();
+        assert_eq!(viewport.physical_size, UVec2::new(1000, 500));
+        assert_eq!(viewport.physical_position, UVec2::new(0, 250));
+        assert_eq!(scale * 1000., 40.);
+
+        let (viewport, scale) = fit.fit(UVec2::new(3000, 1000), 2.).unwrap();
+        let viewport = viewport.unwrap();
+        assert_eq!(viewport.physical_size, UVec2::new(2000, 1000));
+        assert_eq!(viewport.physical_position, UVec2::new(500, 0));
+        assert_eq!(scale * 500., 20.);
+    }
+
+    #[test]
+    fn minimised_windows_are_ignored() {
+        assert!(CameraFit::default().fit(UVec2::new(0, 0), 1.).is_none());
+    }
+}
@@ -1,8 +1,24 @@
 use bevy::prelude::*;
+use bevy::window::WindowResized;
 
-use crate::camera::camera_components::{CameraFrame, FocalPoint};
+use crate::camera::camera_components::{CameraFit, CameraFrame, FocalPoint};
+use crate::config::settings::display_settings::DisplaySettings;
+use crate::levels::tiles::tilemap::TileMap;
+use crate::systems::menu::setup_hud::hud_right_edge;
 use crate::systems::motion::structs::direction::Direction2D;
 
+type FitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        ChangeTrackers<CameraFit>,
+        &'static CameraFit,
+        &'static mut Camera,
+        &'static mut OrthographicProjection,
+        &'static mut Transform,
+    ),
+>;
+
 /// This system handles player input to control certain aspects of the camera.
 /// Specifically: camera panning, camera zoom.
 pub fn camera_control(
@@ -56,3 +72,53 @@ pub fn camera_follow_focal_point(
     transform.translation.x = target_pos.x + frame.pan.x;
     transform.translation.y = target_pos.y + frame.pan.y;
 }
+
+/// Scales the camera to the window whenever the window is resized, or whenever the camera is told
+/// to show a different part of the world.
+pub fn fit_camera_to_window(
+    mut resized: EventReader<WindowResized>,
+    windows: Res<Windows>,
+    mut query: FitQuery,
+) {
+    let window_resized = resized.iter().last().is_some();
+    let window = match windows.get_primary() {
+        Some(window) => window,
+        None => return,
+    };
+    let physical_size = UVec2::new(window.physical_width(), window.physical_height());
+    for (tracker, fit, mut camera, mut projection, mut transform) in query.iter_mut() {
+        if !window_resized && !tracker.is_changed() {
+            continue;
+        }
+        if let Some((viewport, scale)) = fit.fit(physical_size, window.scale_factor() as f32) {
+            camera.viewport = viewport;
+            transform.scale = Vec3::new(scale, scale, 1.);
+            // Bevy only recalculates the projection for the new viewport if it thinks the
+            // projection itself has changed.
+            projection.set_changed();
+        }
+    }
+}
+
+/// Makes the camera show the whole level, plus the HUD next to it. If the display settings ask
+/// for a fixed number of rows instead, the camera shows that many rows.
+pub fn fit_camera_to_level(
+    tile_map: Res<TileMap>,
+    settings: Res<DisplaySettings>,
+    mut query: Query<&mut CameraFit>,
+) {
+    let bounds = &tile_map.world_bounds;
+    let fit = match settings.level_rows {
+        Some(rows) => CameraFit::Rows(rows),
+        None => {
+            // The camera is centered on the level, so leave as much room on the left as the HUD
+            // needs on the right.
+            let center = bounds.x() as f32 + bounds.width() as f32 / 2.;
+            let half_width = (bounds.width() as f32 / 2.).max(hud_right_edge(bounds) - center);
+            CameraFit::Area(Vec2::new(half_width * 2., bounds.height() as f32))
+        }
+    };
+    for mut camera_fit in query.iter_mut() {
+        *camera_fit = fit;
+    }
+}
@@ -0,0 +1,193 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+
+use crate::config::settings::debug_settings::DebugSettings;
+
+pub const USAGE: &str = "\
+Usage: dwarf_seeks_fortune [options]
+
+Options:
+    --play LEVEL           Skip the menus and play the given level file.
+    --edit LEVEL           Skip the menus and open the given level file in the editor.
+                           If the file does not exist, the editor starts with an empty level.
+    --window MODE          Open the window as: windowed, borderless or fullscreen.
+    --size WIDTHxHEIGHT    Open the window with the given size. Implies --window windowed,
+                           unless another mode is given.
+    --debug FIELD=VALUE    Override a field of the debug settings for this run. The value is
+                           written in RON, for example: --debug time_scale=0.5
+    --help                 Print this message.";
+
+/// Options given on the command line when the game is started. They take precedence over the
+/// settings files, but only for this run; they are never written back.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaunchOptions {
+    /// If set, the game opens this level file in the InGame state, skipping the menus.
+    pub play: Option<PathBuf>,
+    /// If set, the game opens this level file in the level editor, skipping the menus.
+    pub edit: Option<PathBuf>,
+    pub window_mode: Option<WindowMode>,
+    pub window_size: Option<Vec2>,
+    /// Overrides for fields of the `DebugSettings`: field names, paired with values in RON.
+    pub debug: Vec<(String, String)>,
+}
+
+impl LaunchOptions {
+    /// Parses the command line arguments, without the name of the executable.
+    /// Returns None if the user only asked for help.
+    pub fn parse<I>(args: I) -> Result<Option<Self>, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = LaunchOptions::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{} needs a value.", arg));
+            match arg.as_str() {
+                "--play" => options.play = Some(readable_file(value()?)?),
+                "--edit" => options.edit = Some(PathBuf::from(value()?)),
+                "--window" => options.window_mode = Some(parse_window_mode(&value()?)?),
+                "--size" => options.window_size = Some(parse_size(&value()?)?),
+                "--debug" => {
+                    let value = value()?;
+                    let (field, value) = value
+                        .split_once('=')
+                        .ok_or_else(|| format!("Expected FIELD=VALUE, got {:?}.", value))?;
+                    options.debug.push((field.to_string(), value.to_string()));
+                }
+                "--help" | "-h" => return Ok(None),
+                _ => return Err(format!("Unknown option {:?}.", arg)),
+            }
+        }
+        if options.play.is_some() && options.edit.is_some() {
+            return Err("Use either --play or --edit, not both.".to_string());
+        }
+        if options.window_size.is_some() && options.window_mode.is_none() {
+            options.window_mode = Some(WindowMode::Windowed);
+        }
+        // Catch misspelled fields and malformed values now, rather than after loading.
+        options.override_debug_settings(&DebugSettings::default())?;
+        Ok(Some(options))
+    }
+
+    /// Returns a copy of the given settings, with the fields given on the command line replaced.
+    pub fn override_debug_settings(
+        &self,
+        settings: &DebugSettings,
+    ) -> Result<DebugSettings, String> {
+        let data = ron::to_string(settings).map_err(|error| error.to_string())?;
+        let mut value = ron::from_str::<ron::Value>(&data).map_err(|error| error.to_string())?;
+        if let ron::Value::Map(fields) = &mut value {
+            for (field, field_value) in &self.debug {
+                let field_value = ron::from_str::<ron::Value>(field_value)
+                    .map_err(|error| format!("Invalid value for {}: {}", field, error))?;
+                fields.insert(ron::Value::String(field.clone()), field_value);
+            }
+        }
+        value
+            .into_rust::<DebugSettings>()
+            .map_err(|error| format!("Invalid debug settings: {}", error))
+    }
+}
+
+/// Checks that the level file can be read now, so a typo does not surface only after loading.
+fn readable_file(value: String) -> Result<PathBuf, String> {
+    let path = PathBuf::from(value);
+    fs::read_to_string(&path).map_err(|error| format!("Cannot read {:?}: {}", path, error))?;
+    Ok(path)
+}
+
+fn parse_window_mode(value: &str) -> Result<WindowMode, String> {
+    match value {
+        "windowed" => Ok(WindowMode::Windowed),
+        "borderless" => Ok(WindowMode::BorderlessFullscreen),
+        "fullscreen" => Ok(WindowMode::Fullscreen),
+        _ => Err(format!("Unknown window mode {:?}.", value)),
+    }
+}
+
+fn parse_size(value: &str) -> Result<Vec2, String> {
+    let parse = |length: &str| {
+        length
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|length| *length > 0.)
+    };
+    value
+        .split_once('x')
+        .and_then(|(width, height)| Some(Vec2::new(parse(width)?, parse(height)?)))
+        .ok_or_else(|| format!("Expected a size like 1200x800, got {:?}.", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    /// The tests run from the dsf_core directory, so the level files are one directory up.
+    const LEVEL: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/world/levels/confined.ron"
+    );
+
+    fn parse(args: &[&str]) -> Result<Option<LaunchOptions>, String> {
+        LaunchOptions::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn parses_options() {
+        let options = parse(&[
+            "--play",
+            LEVEL,
+            "--size",
+            "1200x800",
+            "--debug",
+            "time_scale=0.5",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(options.play.as_deref(), Some(Path::new(LEVEL)));
+        assert_eq!(options.window_size, Some(Vec2::new(1200., 800.)));
+        assert_eq!(options.window_mode, Some(WindowMode::Windowed));
+        assert_eq!(parse(&[]).unwrap(), Some(LaunchOptions::default()));
+        assert_eq!(parse(&["--help"]).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        assert!(parse(&["--play"]).is_err());
+        assert!(parse(&["--play", "no_such_level.ron"]).is_err());
+        assert!(parse(&["--play", LEVEL, "--edit", "b.ron"]).is_err());
+        assert!(parse(&["--window", "huge"]).is_err());
+        assert!(parse(&["--size", "1200"]).is_err());
+        assert!(parse(&["--debug", "time_scale"]).is_err());
+        assert!(parse(&["--debug", "no_such_field=true"]).is_err());
+        assert!(parse(&["--debug", "time_scale=fast"]).is_err());
+        assert!(parse(&["--fast"]).is_err());
+    }
+
+    #[test]
+    fn overrides_debug_settings() {
+        let options = parse(&[
+            "--debug",
+            "use_alternate_menu=false",
+            "--debug",
+            "time_scale_presets=[0.5, 1]",
+        ])
+        .unwrap()
+        .unwrap();
+        let settings = DebugSettings {
+            time_scale: 2.,
+            use_alternate_menu: true,
+            ..DebugSettings::default()
+        };
+        let settings = options.override_debug_settings(&settings).unwrap();
+        assert!(!settings.use_alternate_menu);
+        assert_eq!(settings.time_scale_presets, vec![0.5, 1.]);
+        assert_eq!(settings.time_scale, 2.);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod editor_config;
+pub mod launch_options;
 pub mod loading_config;
 pub mod movement_config;
 pub mod settings;
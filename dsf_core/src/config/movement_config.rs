@@ -2,6 +2,7 @@ use std::fs;
 
 use serde::{Deserialize, Serialize};
 
+use crate::systems::motion::structs::jump_arc::JumpArc;
 use crate::util::files::get_config_dir;
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
@@ -19,6 +20,13 @@ pub struct MovementConfig {
     /// starts walking. This gives the player a bit of time to let go of the walking controls if
     /// they just want to turn around, but not want to start walking.
     pub turn_allowance: f32,
+    /// How many seconds a jump is remembered when it cannot be performed right away. If the
+    /// player presses jump just before landing, the jump happens as soon as they touch the ground.
+    #[serde(default)]
+    pub jump_buffer: f32,
+    /// How many seconds a character can still jump after walking off a ledge.
+    #[serde(default)]
+    pub coyote_time: f32,
     /// When the player first starts pressing down a movement key (e.g. RIGHT), how many seconds
     /// does it take between moving the first step and moving the second step? The first step is
     /// taken instantly, the second step takes a while. This prevents a single key press registering
@@ -28,6 +36,13 @@ pub struct MovementConfig {
     /// steps? The first step takes longer, that's what the high cooldown is for. Each subsequent
     /// step takes much shorter.
     pub map_cursor_move_low_cooldown: f32,
+    /// The greatest height (in tiles) that a character can fall without dying. Characters that
+    /// fall any further than this die when they land. If this is not set, falls are never lethal.
+    #[serde(default)]
+    pub max_safe_fall_height: Option<f32>,
+    /// How high and how fast characters jump and how fast they fall.
+    #[serde(default)]
+    pub jump: JumpArc,
 }
 
 impl MovementConfig {
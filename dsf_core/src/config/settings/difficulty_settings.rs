@@ -0,0 +1,60 @@
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::util::files::{get_default_settings_dir, get_user_settings_dir};
+
+/// To change the default settings, check out the `assets/config/default_settings/difficulty.ron`
+/// file.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DifficultySettings {
+    /// If true, the player only has a limited number of lives. Once they are all used up, the
+    /// game is over. Resetting the level does not cost a life. If false, the player can keep
+    /// retrying a level forever.
+    pub limited_lives: bool,
+    /// How many lives the player starts out with. Only used if `limited_lives` is true.
+    pub starting_lives: u32,
+    /// How many tools the player can carry at once. With a single slot, the player must drop the
+    /// tool they are holding before they can pick up another one. With more slots, the player can
+    /// switch between the tools they carry.
+    #[serde(default = "default_inventory_slots")]
+    pub inventory_slots: usize,
+}
+
+fn default_inventory_slots() -> usize {
+    1
+}
+
+impl DifficultySettings {
+    /// Loads the most relevant instance of `DifficultySettings`.
+    ///
+    /// If the user `DifficultySettings` file exists, tries to load from user settings first. If that fails,
+    /// log an error and use the Default trait implementation (ie: `DifficultySettings::default()`).
+    ///
+    /// If the user 'DifficultySettings' file does not exist, tries to load the default settings file instead.
+    #[must_use]
+    pub fn load_from_file() -> DifficultySettings {
+        let user_settings_file = get_user_settings_dir().join("difficulty.ron");
+        if user_settings_file.exists() {
+            load_from_path(&user_settings_file)
+        } else {
+            load_from_path(&get_default_settings_dir().join("difficulty.ron"))
+        }
+    }
+}
+
+fn load_from_path(path: &Path) -> DifficultySettings {
+    fs::read_to_string(path)
+        .and_then(|data| ron::de::from_str::<DifficultySettings>(&data).map_err(Error::other))
+        .unwrap_or_else(|error| {
+            error!(
+                    "Failed to load the difficulty settings file from {:?}! Falling back to DifficultySettings::default(). Error: {:?}",
+                    path, error
+                );
+            DifficultySettings::default()
+        })
+}
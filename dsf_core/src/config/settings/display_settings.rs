@@ -0,0 +1,184 @@
+use std::fs;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, WindowMode, WindowPosition};
+use serde::{Deserialize, Serialize};
+
+use crate::config::launch_options::LaunchOptions;
+use crate::util::files::{get_default_settings_dir, get_user_settings_dir, serialise_ron};
+
+/// How the game window is shown.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// A regular window with the configured size.
+    Windowed,
+    /// A window without borders that covers the whole monitor.
+    Borderless,
+    /// Exclusive fullscreen, at the highest resolution the monitor supports.
+    Fullscreen,
+}
+
+impl From<DisplayMode> for WindowMode {
+    fn from(mode: DisplayMode) -> Self {
+        match mode {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+/// To change the default settings, check out the `assets/config/default_settings/display.ron`
+/// file.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DisplaySettings {
+    pub mode: DisplayMode,
+    /// The width and height of the window in logical pixels, when it is not fullscreen.
+    pub size: (u32, u32),
+    /// The index of the monitor to show the game on. If None, the primary monitor is used.
+    pub monitor: Option<usize>,
+    /// If true, frames are only shown when the monitor is ready for them. This prevents tearing.
+    pub vsync: bool,
+    /// While playing, the camera shows this many rows of the level. If None, it shows the whole
+    /// level, letterboxed if the window has a different shape.
+    #[serde(default)]
+    pub level_rows: Option<f32>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        DisplaySettings {
+            mode: DisplayMode::Borderless,
+            size: (1200, 800),
+            monitor: None,
+            vsync: true,
+            level_rows: None,
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// Describes the window that the game should open, taking into account any window options
+    /// that were given on the command line.
+    ///
+    /// Bevy always opens fullscreen windows on the primary monitor. If another monitor is
+    /// configured, the window is opened on that monitor in windowed mode first;
+    /// `apply_display_mode` switches it to fullscreen afterwards.
+    #[must_use]
+    pub fn window_descriptor(&self, options: &LaunchOptions) -> WindowDescriptor {
+        let size = options
+            .window_size
+            .unwrap_or_else(|| Vec2::new(self.size.0 as f32, self.size.1 as f32));
+        WindowDescriptor {
+            width: size.x,
+            height: size.y,
+            position: WindowPosition::Centered(
+                self.monitor
+                    .map_or(MonitorSelection::Primary, MonitorSelection::Number),
+            ),
+            present_mode: if self.vsync {
+                PresentMode::AutoVsync
+            } else {
+                PresentMode::AutoNoVsync
+            },
+            mode: if self.monitor.is_some() {
+                WindowMode::Windowed
+            } else {
+                self.window_mode(options)
+            },
+            ..default()
+        }
+    }
+
+    /// The mode the window should be in, unless the command line says otherwise.
+    #[must_use]
+    pub fn window_mode(&self, options: &LaunchOptions) -> WindowMode {
+        options.window_mode.unwrap_or_else(|| self.mode.into())
+    }
+
+    /// Switch between windowed and borderless fullscreen and write the `DisplaySettings` to a user
+    /// settings file. Returns the new mode.
+    pub fn toggle_fullscreen(&mut self, current: WindowMode) -> WindowMode {
+        self.mode = if current == WindowMode::Windowed {
+            DisplayMode::Borderless
+        } else {
+            DisplayMode::Windowed
+        };
+        self.write_settings(get_user_settings_dir().join("display.ron"));
+        self.mode.into()
+    }
+
+    /// Sets the size of the window when it is not fullscreen and writes the `DisplaySettings` to a
+    /// user settings file, if the size changed.
+    pub fn resize(&mut self, size: (u32, u32)) {
+        if size != self.size && size.0 > 0 && size.1 > 0 {
+            self.size = size;
+            self.write_settings(get_user_settings_dir().join("display.ron"));
+        }
+    }
+
+    fn write_settings(&self, path: PathBuf) {
+        fs::write(
+            path,
+            serialise_ron(self).expect("Failed to serialise DisplaySettings to ron."),
+        )
+        .expect("Failed to write DisplaySettings to file.");
+    }
+
+    /// Loads the most relevant instance of `DisplaySettings`.
+    ///
+    /// If the user `DisplaySettings` file exists, tries to load from user settings first. If that fails,
+    /// print an error and use the Default trait implementation (ie: `DisplaySettings::default()`).
+    ///
+    /// If the user 'DisplaySettings' file does not exist, tries to load the default settings file instead.
+    ///
+    /// The window is described before the app starts, so this runs before the logger exists. That is
+    /// why errors go straight to stderr.
+    #[must_use]
+    pub fn load_from_file() -> DisplaySettings {
+        let user_settings_file = get_user_settings_dir().join("display.ron");
+        if user_settings_file.exists() {
+            load_from_path(&user_settings_file)
+        } else {
+            load_from_path(&get_default_settings_dir().join("display.ron"))
+        }
+    }
+}
+
+fn load_from_path(path: &Path) -> DisplaySettings {
+    fs::read_to_string(path)
+        .and_then(|data| ron::de::from_str::<DisplaySettings>(&data).map_err(Error::other))
+        .unwrap_or_else(|error| {
+            eprintln!(
+                    "Failed to load the display settings file from {:?}! Falling back to DisplaySettings::default(). Error: {:?}",
+                    path, error
+                );
+            DisplaySettings::default()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_options_take_precedence() {
+        let settings: DisplaySettings = ron::de::from_str(include_str!(
+            "../../../../assets/config/default_settings/display.ron"
+        ))
+        .unwrap();
+        let window = settings.window_descriptor(&LaunchOptions::default());
+        assert_eq!(window.mode, WindowMode::BorderlessFullscreen);
+        assert_eq!((window.width, window.height), (1200., 800.));
+
+        let options = LaunchOptions::parse(["--size".to_string(), "640x360".to_string()])
+            .unwrap()
+            .unwrap();
+        let window = settings.window_descriptor(&options);
+        assert_eq!(window.mode, WindowMode::Windowed);
+        assert_eq!((window.width, window.height), (640., 360.));
+    }
+}
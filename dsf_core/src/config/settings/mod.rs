@@ -1,4 +1,6 @@
 pub mod audio_settings;
 pub mod debug_settings;
+pub mod difficulty_settings;
+pub mod display_settings;
 pub mod progression;
 pub mod user_cache;
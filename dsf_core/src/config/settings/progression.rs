@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::{Error, ErrorKind};
 use std::path::{Path, PathBuf};
@@ -12,9 +13,52 @@ use crate::util::files::{get_default_settings_dir, get_user_settings_dir, serial
 pub struct Progression {
     pub current_level: usize,
     pub levels: Vec<String>,
+    /// The player's records for each level they have completed, keyed by level file name.
+    /// These survive a reset of the progression, so the player can try to improve on them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub records: BTreeMap<String, LevelRecord>,
+}
+
+/// The best results the player achieved in a single level.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct LevelRecord {
+    /// How many times the player has completed this level.
+    pub completed: u32,
+    /// The highest score of any completion.
+    pub best_score: u32,
+    /// The fastest completion, in seconds.
+    pub best_time: f32,
+    /// The fewest deaths that it took to complete the level.
+    pub fewest_deaths: usize,
+}
+
+impl LevelRecord {
+    /// Merges the result of a completed attempt into this record.
+    fn update(&mut self, score: u32, time: f32, deaths: usize) {
+        if self.completed == 0 {
+            self.best_score = score;
+            self.best_time = time;
+            self.fewest_deaths = deaths;
+        } else {
+            self.best_score = self.best_score.max(score);
+            self.best_time = self.best_time.min(time);
+            self.fewest_deaths = self.fewest_deaths.min(deaths);
+        }
+        self.completed += 1;
+    }
 }
 
 impl Progression {
+    /// Stores the results of completing the given level, if they improve on the player's records.
+    pub fn record_result(&mut self, level: &str, score: u32, time: f32, deaths: usize) {
+        self.records
+            .entry(level.to_string())
+            .or_default()
+            .update(score, time, deaths);
+        self.write_settings(get_user_settings_dir().join("progression.ron"));
+    }
+
     pub fn increment(&mut self) {
         self.current_level = (self.levels.len() - 1).min(self.current_level + 1);
         self.write_settings(get_user_settings_dir().join("progression.ron"));
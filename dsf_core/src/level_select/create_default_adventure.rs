@@ -11,27 +11,34 @@ use crate::util::files::{get_adventures_dir, get_levels_dir, serialise_ron};
 /// This is useful while there aren't too many levels yet.
 /// This is a debug thing.
 pub fn create_default_adventure() {
+    let skipped = write_default_adventure()
+        .expect("Failed to create default adventure that contains all levels.");
+    for problem in skipped {
+        error!("{}", problem);
+    }
+}
+
+/// Writes the adventure that gives access to every level to `adventures/default.ron`. Levels that
+/// cannot be read are left out; a description of each of them is returned.
+pub fn write_default_adventure() -> Result<Vec<String>, String> {
     let mut adventure = Adventure::default();
-    level_files()
+    let mut skipped = Vec::new();
+    level_files()?
         .iter()
-        .map(|level_name| {
+        .filter(|level_name| {
             let level_file = get_levels_dir().join(level_name);
-            let data = fs::read_to_string(level_file).expect("Unable to read level file");
-            let level = ron::de::from_str::<LevelSave>(&data);
-            (level_name, level)
-        })
-        .filter(|(level_name, result)| {
-            result
-                .as_ref()
-                .map_err(|err| {
-                    error!("Failed to load level {:?}: {:?}", level_name, err);
-                    err
-                })
-                .is_ok()
+            let level = fs::read_to_string(level_file)
+                .map_err(|error| error.to_string())
+                .and_then(|data| {
+                    ron::de::from_str::<LevelSave>(&data).map_err(|error| error.to_string())
+                });
+            if let Err(error) = &level {
+                skipped.push(format!("Failed to load level {:?}: {}", level_name, error));
+            }
+            level.is_ok()
         })
-        .map(|(level_name, result)| (level_name, result.expect("Should never panic.")))
         .enumerate()
-        .for_each(|(index, (level_name, _level))| {
+        .for_each(|(index, level_name)| {
             adventure.nodes.insert(
                 Pos::new((index * 2) as i32, 0),
                 MapElement::Node(AdventureNode {
@@ -46,16 +53,15 @@ pub fn create_default_adventure() {
             }
         });
 
-    fs::write(
-        get_adventures_dir().join("default.ron"),
-        serialise_ron(&adventure).expect("Failed to serialise adventure."),
-    )
-    .expect("Failed to create default adventure that contains all levels.");
+    let data = serialise_ron(&adventure).map_err(|error| error.to_string())?;
+    fs::write(get_adventures_dir().join("default.ron"), data)
+        .map_err(|error| format!("Unable to write the default adventure: {}", error))?;
+    Ok(skipped)
 }
 
-fn level_files() -> Vec<String> {
-    fs::read_dir(get_levels_dir())
-        .expect("Failed to read contents of the levels directory.")
+fn level_files() -> Result<Vec<String>, String> {
+    let files = fs::read_dir(get_levels_dir())
+        .map_err(|error| format!("Unable to read the levels directory: {}", error))?
         .filter_map(|file| {
             if let Ok(file) = file {
                 if file.path().is_file() {
@@ -74,5 +80,6 @@ fn level_files() -> Vec<String> {
                 None
             }
         })
-        .collect()
+        .collect();
+    Ok(files)
 }
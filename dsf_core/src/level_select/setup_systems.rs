@@ -6,8 +6,9 @@ use bevy::prelude::*;
 use crate::camera::camera_components::FocalPoint;
 use crate::config::settings::user_cache::UserCache;
 use crate::level_select::structs::{
-    Adventure, LevelSelectionInstruction, MapCursor, MapElement, PositionOnMap,
+    Adventure, LevelSelectionInstruction, LevelThumbnail, MapCursor, MapElement, PositionOnMap,
 };
+use crate::level_select::update_systems::ThumbnailCache;
 use crate::levels::tiles::tile_defs::DepthLayer;
 use crate::loading::assets::{AssetStorage, SpriteType};
 use crate::systems::motion::structs::pos::Pos;
@@ -22,6 +23,7 @@ pub fn on_start(
     let path = instruction.adventure.as_ref().expect("No adventure!");
     load_adventure(path, &mut commands, &user_cache, &storage);
     load_cursor(&mut commands, &storage, &Pos::new(0, 0));
+    load_thumbnail(&mut commands);
 }
 
 pub fn load_adventure(
@@ -70,6 +72,17 @@ fn load_cursor(commands: &mut Commands, storage: &Res<AssetStorage>, pos: &Pos)
         .insert(FocalPoint);
 }
 
+/// The thumbnail stays hidden until the cursor is on a level.
+fn load_thumbnail(commands: &mut Commands) {
+    commands.insert_resource(ThumbnailCache::load());
+    commands
+        .spawn_bundle(SpriteBundle {
+            visibility: Visibility { is_visible: false },
+            ..default()
+        })
+        .insert(LevelThumbnail);
+}
+
 fn spawn_road(pos: &Pos, commands: &mut Commands, storage: &Res<AssetStorage>) {
     let bundle = create_indexed(pos, storage, 1);
     commands.spawn_bundle(bundle);
@@ -15,6 +15,10 @@ pub struct MapCursor {
     pub cooldown: f32,
 }
 
+/// Shows a preview of the level that the map cursor is on.
+#[derive(Clone, Copy, Debug, Default, Component)]
+pub struct LevelThumbnail;
+
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct PositionOnMap {
     pub pos: Pos,
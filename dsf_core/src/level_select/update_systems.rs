@@ -1,13 +1,21 @@
+use std::collections::HashMap;
+use std::fs;
+
 use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 use iyes_loopless::prelude::NextState;
 
 use crate::audio::sound_event::SoundEvent;
 use crate::config::movement_config::MovementConfig;
 use crate::level_select::structs::{
-    Adventure, AdventureNode, LevelSelectionInstruction, MapCursor, MapElement, NodeDetails,
-    PositionOnMap,
+    Adventure, AdventureNode, LevelSelectionInstruction, LevelThumbnail, MapCursor, MapElement,
+    NodeDetails, PositionOnMap,
 };
+use crate::levels::level_save::LevelSave;
+use crate::levels::thumbnail::ThumbnailRenderer;
+use crate::levels::tiles::tile_defs::DepthLayer;
 use crate::loading::assets::SoundType;
+use crate::loading::levels::load_level_system::load_tile_definitions;
 use crate::states::AppState;
 use crate::systems::motion::structs::direction::Direction2D;
 use crate::util::files::get_levels_dir;
@@ -108,6 +116,128 @@ fn move_cursor(
     }
 }
 
+/// The width of the level thumbnail on the map, in tiles.
+const THUMBNAIL_WIDTH: f32 = 6.;
+/// The number of pixels that every tile of the level gets in its thumbnail.
+const THUMBNAIL_TILE_PIXELS: u32 = 8;
+
+type ThumbnailQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Handle<Image>,
+        &'static mut Sprite,
+        &'static mut Transform,
+        &'static mut Visibility,
+    ),
+    With<LevelThumbnail>,
+>;
+
+/// Thumbnails are only rendered the first time their level is selected. A new cache is made every
+/// time the level select screen is entered, so levels that were changed in the meantime are shown
+/// as they are now.
+pub struct ThumbnailCache {
+    renderer: Option<ThumbnailRenderer>,
+    /// Holds None for levels that could not be rendered, so they are not tried again.
+    images: HashMap<String, Option<Handle<Image>>>,
+}
+
+/// Shows a thumbnail of the level that the cursor is on, just above the cursor.
+pub fn show_thumbnail(
+    pos_on_map: Res<PositionOnMap>,
+    adventure: Res<Adventure>,
+    mut images: ResMut<Assets<Image>>,
+    mut cache: ResMut<ThumbnailCache>,
+    mut query: ThumbnailQuery,
+) {
+    if !pos_on_map.is_changed() {
+        return;
+    }
+    let level_name = match adventure.nodes.get(&pos_on_map.pos) {
+        Some(MapElement::Node(AdventureNode {
+            details: NodeDetails::Level(level_name),
+            ..
+        })) => Some(level_name),
+        _ => None,
+    };
+    let image = level_name.and_then(|level_name| cache.get(level_name, &mut images));
+    for (mut handle, mut sprite, mut transform, mut visibility) in query.iter_mut() {
+        visibility.is_visible = image.is_some();
+        if let Some((image, size)) = &image {
+            *handle = image.clone();
+            let height = THUMBNAIL_WIDTH * size.y / size.x;
+            sprite.custom_size = Some(Vec2::new(THUMBNAIL_WIDTH, height));
+            transform.translation = Vec3::new(
+                pos_on_map.pos.x as f32 + 0.5,
+                pos_on_map.pos.y as f32 + 1.5 + height * 0.5,
+                DepthLayer::UiElements.z(),
+            );
+        }
+    }
+}
+
+impl ThumbnailCache {
+    /// Reads the tile definitions and decodes the sprite sheets that thumbnails are drawn with.
+    pub fn load() -> Self {
+        let renderer = ThumbnailRenderer::load(load_tile_definitions())
+            .map_err(|error| error!("Unable to set up the thumbnail renderer: {}", error))
+            .ok();
+        ThumbnailCache {
+            renderer,
+            images: HashMap::new(),
+        }
+    }
+
+    /// The thumbnail of the level with the given file name and its size in pixels.
+    fn get(
+        &mut self,
+        level_name: &str,
+        images: &mut Assets<Image>,
+    ) -> Option<(Handle<Image>, Vec2)> {
+        if !self.images.contains_key(level_name) {
+            let handle = self.render(level_name).map(|image| images.add(image));
+            self.images.insert(level_name.to_string(), handle);
+        }
+        let handle = self.images.get(level_name)?.clone()?;
+        let size = images.get(&handle)?.size();
+        Some((handle, size))
+    }
+
+    fn render(&self, level_name: &str) -> Option<Image> {
+        let renderer = self.renderer.as_ref()?;
+        let path = get_levels_dir().join(level_name);
+        let level = fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|data| {
+                ron::de::from_str::<LevelSave>(&data).map_err(|error| error.to_string())
+            });
+        let level = match level {
+            Ok(level) => level,
+            Err(error) => {
+                warn!(
+                    "Unable to read level {:?} for its thumbnail: {}",
+                    path, error
+                );
+                return None;
+            }
+        };
+        let thumbnail = renderer.render(&level, THUMBNAIL_TILE_PIXELS);
+        let (width, height) = thumbnail.dimensions();
+        (width > 0 && height > 0).then(|| {
+            Image::new(
+                Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                thumbnail.into_raw(),
+                TextureFormat::Rgba8UnormSrgb,
+            )
+        })
+    }
+}
+
 /// Updates the UI label on the adventure and level select screen. The label must always display the
 /// name of the currently selected node.
 pub fn update_ui() {}
@@ -0,0 +1,385 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::levels::level_save::LevelSave;
+use crate::levels::tiles::tile_defs::TileDefinitions;
+use crate::levels::world_bounds::WorldBounds;
+use crate::systems::motion::structs::pos::Pos;
+
+/// Marks an empty cell in an ASCII map. Spaces are read as empty cells too, but dots are easier to
+/// count and do not get lost when an editor trims trailing whitespace.
+const EMPTY: char = '.';
+
+/// Maps the characters in an ASCII map to tile definition keys.
+#[derive(Debug, Clone)]
+pub struct AsciiLegend {
+    tiles: BTreeMap<char, String>,
+}
+
+impl Default for AsciiLegend {
+    fn default() -> Self {
+        AsciiLegend {
+            tiles: BTreeMap::new(),
+        }
+        .with('#', "Block1")
+        .with('=', "Block2")
+        .with('H', "Ladder")
+        .with('P', "Player")
+        .with('K', "Key")
+        .with('D', "Door")
+        .with('T', "TrapWall")
+        .with('^', "Spikes")
+        .with('>', "RevolvingDoorFacingPositive")
+        .with('<', "RevolvingDoorFacingNegative")
+        .with('X', "ToolPickaxe")
+    }
+}
+
+impl AsciiLegend {
+    /// Maps the given character to the given tile definition key, replacing any earlier mapping
+    /// of that character.
+    #[must_use]
+    pub fn with(mut self, symbol: char, key: &str) -> Self {
+        self.tiles.insert(symbol, key.to_string());
+        self
+    }
+
+    /// The tile definition key for the given character, if it is in the legend.
+    #[must_use]
+    pub fn key(&self, symbol: char) -> Option<&String> {
+        self.tiles.get(&symbol)
+    }
+
+    /// The character for the given tile definition key. If multiple characters map to the same
+    /// key, the lowest of those characters is used.
+    #[must_use]
+    pub fn symbol(&self, key: &str) -> Option<char> {
+        self.tiles
+            .iter()
+            .find(|(_, tile)| *tile == key)
+            .map(|(symbol, _)| *symbol)
+    }
+}
+
+/// Reasons why a level cannot be converted to or from an ASCII map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsciiError {
+    /// The map contains a character that is not in the legend.
+    UnknownSymbol(char, Pos),
+    /// The legend maps a character to a key that has no tile definition.
+    UnknownTile(String),
+    /// The level contains a tile that has no character in the legend.
+    NoSymbol(String),
+    /// The tile at the given position does not fit in the map; its footprint is either cut off
+    /// by the edge of the map or drawn incompletely.
+    BrokenFootprint(String, Pos),
+    /// The tile at the given position overlaps another tile.
+    Overlap(String, Pos),
+    /// The level has wiring or platform paths, which cannot be drawn in ASCII.
+    WiringOrPaths,
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsciiError::UnknownSymbol(symbol, pos) => {
+                write!(f, "unknown symbol {:?} at {:?}", symbol, pos)
+            }
+            AsciiError::UnknownTile(key) => write!(f, "no tile definition for {:?}", key),
+            AsciiError::NoSymbol(key) => write!(f, "no symbol in the legend for {:?}", key),
+            AsciiError::BrokenFootprint(key, pos) => {
+                write!(f, "{:?} at {:?} is cut off or drawn incompletely", key, pos)
+            }
+            AsciiError::Overlap(key, pos) => {
+                write!(f, "{:?} at {:?} overlaps another tile", key, pos)
+            }
+            AsciiError::WiringOrPaths => write!(f, "wiring and paths cannot be drawn in ASCII"),
+        }
+    }
+}
+
+impl std::error::Error for AsciiError {}
+
+/// Reads a level from an ASCII map, where each character is one cell of the world.
+///
+/// The top line of the map is the top row of the world. The world bounds are exactly as large as
+/// the map, with their lower left corner at the given origin. Lines that are empty or only contain
+/// whitespace are skipped at the start and end of the map, and indentation that all lines share is
+/// ignored, so maps can be written inline in source code.
+///
+/// A tile that is larger than a single cell must be drawn in full: a 2 by 2 player is two lines of
+/// `PP`. The tile is anchored at the lower left of its footprint. The map is read from the bottom
+/// up and from left to right, so two ladders side by side are simply drawn as `HHHH`.
+pub fn from_ascii(
+    map: &str,
+    origin: Pos,
+    legend: &AsciiLegend,
+    tile_defs: &TileDefinitions,
+) -> Result<LevelSave, AsciiError> {
+    let lines = trimmed_lines(map);
+    let height = lines.len() as i32;
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0) as i32;
+    let mut cells: HashMap<Pos, char> = HashMap::new();
+    for (row, line) in lines.iter().enumerate() {
+        for (column, symbol) in line.chars().enumerate() {
+            if symbol != EMPTY && symbol != ' ' {
+                cells.insert(Pos::new(column as i32, height - 1 - row as i32), symbol);
+            }
+        }
+    }
+
+    let mut level = LevelSave {
+        world_bounds: WorldBounds::new(origin.x, origin.y, width, height),
+        ..LevelSave::default()
+    };
+    for y in 0..height {
+        for x in 0..width {
+            let anchor = Pos::new(x, y);
+            let symbol = match cells.get(&anchor) {
+                Some(symbol) => *symbol,
+                None => continue,
+            };
+            let world_pos = Pos::new(origin.x + x, origin.y + y);
+            let key = legend
+                .key(symbol)
+                .ok_or(AsciiError::UnknownSymbol(symbol, world_pos))?;
+            let dimens = tile_defs
+                .map
+                .get(key)
+                .ok_or_else(|| AsciiError::UnknownTile(key.clone()))?
+                .dimens;
+            let footprint = footprint(anchor, dimens.x, dimens.y);
+            if !footprint.iter().all(|pos| cells.get(pos) == Some(&symbol)) {
+                return Err(AsciiError::BrokenFootprint(key.clone(), world_pos));
+            }
+            for pos in footprint {
+                cells.remove(&pos);
+            }
+            level.tiles.insert(world_pos, key.clone());
+        }
+    }
+    Ok(level)
+}
+
+/// Draws the level as an ASCII map that `from_ascii` reads back into the same level, given the
+/// origin of the level's world bounds.
+pub fn to_ascii(
+    level: &LevelSave,
+    legend: &AsciiLegend,
+    tile_defs: &TileDefinitions,
+) -> Result<String, AsciiError> {
+    if !level.wiring.is_empty() || !level.paths.is_empty() {
+        return Err(AsciiError::WiringOrPaths);
+    }
+    let bounds = &level.world_bounds;
+    let mut cells: HashMap<Pos, char> = HashMap::new();
+    let ordered: BTreeMap<_, _> = level.tiles.iter().collect();
+    for (pos, key) in ordered {
+        let symbol = legend
+            .symbol(key)
+            .ok_or_else(|| AsciiError::NoSymbol(key.clone()))?;
+        let dimens = tile_defs
+            .map
+            .get(key)
+            .ok_or_else(|| AsciiError::UnknownTile(key.clone()))?
+            .dimens;
+        if !bounds.encloses(pos, &dimens) {
+            return Err(AsciiError::BrokenFootprint(key.clone(), *pos));
+        }
+        for cell in footprint(*pos, dimens.x, dimens.y) {
+            if cells.insert(cell, symbol).is_some() {
+                return Err(AsciiError::Overlap(key.clone(), *pos));
+            }
+        }
+    }
+    let map = (bounds.y()..bounds.upper_y())
+        .rev()
+        .map(|y| {
+            (bounds.x()..bounds.upper_x())
+                .map(|x| *cells.get(&Pos::new(x, y)).unwrap_or(&EMPTY))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(map)
+}
+
+fn footprint(anchor: Pos, width: i32, height: i32) -> Vec<Pos> {
+    (0..width)
+        .flat_map(|x| (0..height).map(move |y| anchor.append_xy(x, y)))
+        .collect()
+}
+
+/// Drops blank lines at the start and end of the map, and the indentation all other lines share.
+fn trimmed_lines(map: &str) -> Vec<&str> {
+    let lines = map.lines().collect::<Vec<_>>();
+    let first = lines.iter().position(|line| !line.trim().is_empty());
+    let last = lines.iter().rposition(|line| !line.trim().is_empty());
+    let lines = match (first, last) {
+        (Some(first), Some(last)) => &lines[first..=last],
+        _ => return Vec::new(),
+    };
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or(""))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile_defs() -> TileDefinitions {
+        ron::de::from_str(include_str!("../../../assets/world/tile_definitions.ron"))
+            .expect("Unable to deserialise TileDefinitions")
+    }
+
+    fn tiles(level: &LevelSave) -> BTreeMap<Pos, &str> {
+        level
+            .tiles
+            .iter()
+            .map(|(pos, key)| (*pos, key.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn reads_tiles_and_footprints() {
+        let level = from_ascii(
+            "
+            ....HH
+            KK..HH
+            KKPPHH
+            ..PP..
+            ######
+            ",
+            Pos::new(-3, -1),
+            &AsciiLegend::default(),
+            &tile_defs(),
+        )
+        .unwrap();
+        assert_eq!(level.world_bounds.pos, Pos::new(-3, -1));
+        assert_eq!(level.world_bounds.width(), 6);
+        assert_eq!(level.world_bounds.height(), 5);
+        let expected = BTreeMap::from([
+            (Pos::new(-1, 0), "Player"),
+            (Pos::new(-3, 1), "Key"),
+            (Pos::new(1, 1), "Ladder"),
+            (Pos::new(1, 2), "Ladder"),
+            (Pos::new(1, 3), "Ladder"),
+        ])
+        .into_iter()
+        .chain((-3..3).map(|x| (Pos::new(x, -1), "Block1")))
+        .collect::<BTreeMap<_, _>>();
+        assert_eq!(tiles(&level), expected);
+    }
+
+    #[test]
+    fn round_trips() {
+        let map = "\
+            ....HH....\n\
+            PP..HH..KK\n\
+            PP..HH..KK\n\
+            ##########";
+        let defs = tile_defs();
+        let legend = AsciiLegend::default();
+        let level = from_ascii(map, Pos::new(-5, 2), &legend, &defs).unwrap();
+        let expected = BTreeMap::from([
+            (Pos::new(-5, 3), "Player"),
+            (Pos::new(-1, 3), "Ladder"),
+            (Pos::new(-1, 4), "Ladder"),
+            (Pos::new(-1, 5), "Ladder"),
+            (Pos::new(3, 3), "Key"),
+        ])
+        .into_iter()
+        .chain((-5..5).map(|x| (Pos::new(x, 2), "Block1")))
+        .collect::<BTreeMap<_, _>>();
+        assert_eq!(tiles(&level), expected);
+        assert_eq!(to_ascii(&level, &legend, &defs).unwrap(), map);
+
+        let again = from_ascii(
+            &to_ascii(&level, &legend, &defs).unwrap(),
+            level.world_bounds.pos,
+            &legend,
+            &defs,
+        )
+        .unwrap();
+        assert_eq!(tiles(&again), tiles(&level));
+        assert_eq!(again.world_bounds.dimens, level.world_bounds.dimens);
+    }
+
+    #[test]
+    fn side_by_side_tiles_are_read_left_to_right() {
+        let level = from_ascii(
+            "HHHH",
+            Pos::new(0, 0),
+            &AsciiLegend::default(),
+            &tile_defs(),
+        )
+        .unwrap();
+        let expected = BTreeMap::from([(Pos::new(0, 0), "Ladder"), (Pos::new(2, 0), "Ladder")]);
+        assert_eq!(tiles(&level), expected);
+    }
+
+    #[test]
+    fn rejects_broken_maps() {
+        let defs = tile_defs();
+        let legend = AsciiLegend::default();
+        let read = |map: &str| from_ascii(map, Pos::new(0, 0), &legend, &defs).err();
+        assert_eq!(
+            read("#?"),
+            Some(AsciiError::UnknownSymbol('?', Pos::new(1, 0)))
+        );
+        assert_eq!(
+            read("P.\nPP"),
+            Some(AsciiError::BrokenFootprint("Player".into(), Pos::new(0, 0)))
+        );
+        assert_eq!(
+            read("HHH"),
+            Some(AsciiError::BrokenFootprint("Ladder".into(), Pos::new(2, 0)))
+        );
+        let unknown = AsciiLegend::default().with('?', "NoSuchTile");
+        assert_eq!(
+            from_ascii("?", Pos::new(0, 0), &unknown, &defs).err(),
+            Some(AsciiError::UnknownTile("NoSuchTile".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_levels_that_cannot_be_drawn() {
+        let defs = tile_defs();
+        let legend = AsciiLegend::default();
+        let mut level = from_ascii("PP.\nPP.", Pos::new(0, 0), &legend, &defs).unwrap();
+        level.tiles.insert(Pos::new(1, 0), "Block1".into());
+        assert_eq!(
+            to_ascii(&level, &legend, &defs).err(),
+            Some(AsciiError::Overlap("Block1".into(), Pos::new(1, 0)))
+        );
+        level.tiles.remove(&Pos::new(1, 0));
+        level.tiles.insert(Pos::new(2, 1), "Ladder".into());
+        assert_eq!(
+            to_ascii(&level, &legend, &defs).err(),
+            Some(AsciiError::BrokenFootprint("Ladder".into(), Pos::new(2, 1)))
+        );
+        level.tiles.insert(Pos::new(2, 1), "Gem".into());
+        assert_eq!(
+            to_ascii(&level, &legend, &defs).err(),
+            Some(AsciiError::NoSymbol("Gem".into()))
+        );
+        level.tiles.remove(&Pos::new(2, 1));
+        level.wiring.insert(Pos::new(0, 0), "a".into());
+        assert_eq!(
+            to_ascii(&level, &legend, &defs).err(),
+            Some(AsciiError::WiringOrPaths)
+        );
+    }
+}
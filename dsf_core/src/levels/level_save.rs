@@ -8,7 +8,7 @@ use crate::systems::motion::structs::pos::Pos;
 /// Describes a complete level. This is the format that the level is stored in.
 /// Contains a map of positions, mapped to tile definitions.
 /// This struct can be loaded from a level file and used to start a game.
-#[derive(Debug, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct LevelSave {
     /// The level's exterior borders. In this game, the world wraps at the borders.
@@ -17,14 +17,29 @@ pub struct LevelSave {
     /// These keys can be used to look up the corresponding TileDefinition.
     #[serde(serialize_with = "ordered_map")]
     pub tiles: HashMap<Pos, String>,
+    /// Mapping of tile positions to the name of the signal that tile is wired to.
+    /// Triggers at these positions emit that signal, receivers at these positions react to it.
+    /// The positions are the anchor positions of the tiles, the same as the keys in `tiles`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(serialize_with = "ordered_map")]
+    pub wiring: HashMap<Pos, String>,
+    /// Mapping of moving platform positions to the waypoints that platform travels along.
+    /// The platform starts at its own position, visits every waypoint in order and then returns
+    /// to its starting position, after which it starts over. Waypoints may lie outside the world
+    /// bounds, in which case the platform wraps around the edge of the world.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(serialize_with = "ordered_map")]
+    pub paths: HashMap<Pos, Vec<Pos>>,
 }
 
-/// A function used by serde to serialise the tile map in a deterministic way.
+/// A function used by serde to serialise the tile map (and the wiring and paths) in a
+/// deterministic way.
 /// This will prevent the output being different each time the level is saved, which will
 /// prevent lots of unnecessarily large diffs in the git commits.
-fn ordered_map<S>(value: &HashMap<Pos, String>, serializer: S) -> Result<S::Ok, S::Error>
+fn ordered_map<S, V>(value: &HashMap<Pos, V>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
+    V: Serialize,
 {
     let ordered: BTreeMap<_, _> = value.iter().collect();
     ordered.serialize(serializer)
@@ -1,3 +1,7 @@
+pub mod ascii;
 pub mod level_save;
+pub mod thumbnail;
+pub mod tiled;
 pub mod tiles;
+pub mod validation;
 pub mod world_bounds;
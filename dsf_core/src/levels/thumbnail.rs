@@ -0,0 +1,219 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::imageops::{self, FilterType};
+use image::{ImageError, RgbaImage};
+
+use crate::config::loading_config::LoadingConfig;
+use crate::levels::level_save::LevelSave;
+use crate::levels::tiles::tile_defs::{DepthLayer, TileDefinition, TileDefinitions};
+use crate::loading::assets::{AssetType, SpriteType};
+use crate::loading::atlas_prefab::AtlasPrefab;
+use crate::util::files::{get_assets_dir, get_atlases_dir};
+
+/// Reasons why the thumbnail renderer cannot be set up.
+#[derive(Debug)]
+pub enum ThumbnailError {
+    /// An atlas file could not be read or deserialised.
+    Atlas(PathBuf, String),
+    /// A texture could not be read or decoded.
+    Texture(PathBuf, ImageError),
+}
+
+impl fmt::Display for ThumbnailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThumbnailError::Atlas(path, message) => write!(f, "atlas {:?}: {}", path, message),
+            ThumbnailError::Texture(path, error) => write!(f, "texture {:?}: {}", path, error),
+        }
+    }
+}
+
+impl std::error::Error for ThumbnailError {}
+
+/// A texture that is cut up into a grid of sprites, the same way the game turns an `AtlasPrefab`
+/// into a `TextureAtlas`.
+pub struct SpriteSheet {
+    texture: RgbaImage,
+    prefab: AtlasPrefab,
+}
+
+impl SpriteSheet {
+    pub fn new(texture: RgbaImage, prefab: AtlasPrefab) -> Self {
+        SpriteSheet { texture, prefab }
+    }
+
+    /// Reads the atlas file with the given path and the texture it refers to.
+    pub fn load(path: &Path) -> Result<Self, ThumbnailError> {
+        let data = fs::read_to_string(path)
+            .map_err(|error| ThumbnailError::Atlas(path.to_path_buf(), error.to_string()))?;
+        let prefab = ron::de::from_str::<AtlasPrefab>(&data)
+            .map_err(|error| ThumbnailError::Atlas(path.to_path_buf(), error.to_string()))?;
+        let texture_path = get_assets_dir().join("textures/").join(&prefab.texture);
+        let texture = image::open(&texture_path)
+            .map_err(|error| ThumbnailError::Texture(texture_path, error))?
+            .into_rgba8();
+        Ok(SpriteSheet { texture, prefab })
+    }
+
+    /// The sprite with the given index, or None if the sheet does not have that many sprites.
+    fn sprite(&self, index: usize) -> Option<RgbaImage> {
+        let (width, height) = (
+            self.prefab.tile_size.x as u32,
+            self.prefab.tile_size.y as u32,
+        );
+        let (column, row) = (index % self.prefab.columns, index / self.prefab.columns);
+        let (x, y) = (column as u32 * width, row as u32 * height);
+        let fits = row < self.prefab.rows
+            && x + width <= self.texture.width()
+            && y + height <= self.texture.height();
+        fits.then(|| imageops::crop_imm(&self.texture, x, y, width, height).to_image())
+    }
+}
+
+/// Draws levels into images on the CPU, without a window or a GPU. Tiles are drawn with the same
+/// sprites and colors as the editor shows them, on top of the level background.
+pub struct ThumbnailRenderer {
+    sheets: HashMap<SpriteType, SpriteSheet>,
+    tile_defs: TileDefinitions,
+}
+
+impl ThumbnailRenderer {
+    pub fn new(sheets: HashMap<SpriteType, SpriteSheet>, tile_defs: TileDefinitions) -> Self {
+        ThumbnailRenderer { sheets, tile_defs }
+    }
+
+    /// Loads every sprite sheet that the loading config lists.
+    pub fn load(tile_defs: TileDefinitions) -> Result<Self, ThumbnailError> {
+        let sheets = LoadingConfig::load_from_file()
+            .atlases
+            .into_iter()
+            .map(|(sprite_type, path)| {
+                SpriteSheet::load(&get_atlases_dir().join(path)).map(|sheet| (sprite_type, sheet))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(ThumbnailRenderer::new(sheets, tile_defs))
+    }
+
+    /// Draws the level, using the given number of pixels for every tile.
+    #[must_use]
+    pub fn render(&self, level: &LevelSave, tile_pixels: u32) -> RgbaImage {
+        let bounds = &level.world_bounds;
+        let (width, height) = (
+            bounds.width().max(0) as u32 * tile_pixels,
+            bounds.height().max(0) as u32 * tile_pixels,
+        );
+        let mut canvas = RgbaImage::from_pixel(width, height, image::Rgba([0, 0, 0, 255]));
+        if let Some(background) = self.sprite(AssetType(SpriteType::Background, 0)) {
+            let background = imageops::thumbnail(&background, width, height);
+            imageops::overlay(&mut canvas, &background, 0, 0);
+        }
+        // Draw the tiles back to front, the same way the game layers them.
+        let ordered: BTreeMap<_, _> = level.tiles.iter().collect();
+        let mut tiles = ordered
+            .into_iter()
+            .map(|(pos, key)| (pos, self.tile_defs.get(key)))
+            .collect::<Vec<_>>();
+        tiles.sort_by(|(_, a), (_, b)| a.depth.z().total_cmp(&b.depth.z()));
+        for (pos, tile_def) in tiles {
+            if let DepthLayer::Background = tile_def.depth {
+                continue;
+            }
+            let sprite = match self.tile_sprite(tile_def, tile_pixels) {
+                Some(sprite) => sprite,
+                None => continue,
+            };
+            let x = i64::from(pos.x - bounds.x()) * i64::from(tile_pixels);
+            let y =
+                i64::from(bounds.upper_y() - pos.y - tile_def.dimens.y) * i64::from(tile_pixels);
+            imageops::overlay(&mut canvas, &sprite, x, y);
+        }
+        canvas
+    }
+
+    /// The sprite for the tile, scaled to its size on the canvas and colored the way the game
+    /// colors it.
+    fn tile_sprite(&self, tile_def: &TileDefinition, tile_pixels: u32) -> Option<RgbaImage> {
+        let sprite = self
+            .sprite(tile_def.get_preview())
+            .or_else(|| self.sprite(AssetType(SpriteType::NotFound, 0)))?;
+        let mut sprite = imageops::resize(
+            &sprite,
+            tile_def.dimens.x.max(1) as u32 * tile_pixels,
+            tile_def.dimens.y.max(1) as u32 * tile_pixels,
+            FilterType::Triangle,
+        );
+        if tile_def
+            .revolving()
+            .is_some_and(|door| door.facing.is_negative())
+        {
+            imageops::flip_horizontal_in_place(&mut sprite);
+        }
+        let [red, green, blue, alpha] = tile_def.color().as_rgba_f32();
+        if [red, green, blue, alpha] != [1., 1., 1., 1.] {
+            for pixel in sprite.pixels_mut() {
+                for (channel, factor) in pixel.0.iter_mut().zip([red, green, blue, alpha]) {
+                    *channel = (f32::from(*channel) * factor).round() as u8;
+                }
+            }
+        }
+        Some(sprite)
+    }
+
+    fn sprite(&self, AssetType(sprite_type, index): AssetType) -> Option<RgbaImage> {
+        self.sheets.get(&sprite_type)?.sprite(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use image::Rgba;
+
+    use crate::levels::world_bounds::WorldBounds;
+    use crate::systems::motion::structs::pos::Pos;
+
+    use super::*;
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+    /// A sheet with two 4 by 4 sprites: red and blue.
+    fn sheet() -> SpriteSheet {
+        let texture = RgbaImage::from_fn(8, 4, |x, _| if x < 4 { RED } else { BLUE });
+        SpriteSheet::new(
+            texture,
+            AtlasPrefab {
+                texture: String::new(),
+                tile_size: Vec2::new(4., 4.),
+                columns: 2,
+                rows: 1,
+            },
+        )
+    }
+
+    #[test]
+    fn draws_tiles_at_their_position() {
+        let tile_defs: TileDefinitions =
+            ron::de::from_str(include_str!("../../../assets/world/tile_definitions.ron")).unwrap();
+        let renderer =
+            ThumbnailRenderer::new(HashMap::from([(SpriteType::Blocks, sheet())]), tile_defs);
+        let mut level = LevelSave {
+            world_bounds: WorldBounds::new(-1, 0, 3, 2),
+            ..LevelSave::default()
+        };
+        // Block1 is the first sprite in the sheet, Block2 the second.
+        level.tiles.insert(Pos::new(-1, 0), "Block1".to_string());
+        level.tiles.insert(Pos::new(1, 1), "Block2".to_string());
+        let image = renderer.render(&level, 2);
+        assert_eq!(image.dimensions(), (6, 4));
+        assert_eq!(*image.get_pixel(0, 3), RED);
+        assert_eq!(*image.get_pixel(1, 2), RED);
+        assert_eq!(*image.get_pixel(5, 0), BLUE);
+        assert_eq!(*image.get_pixel(4, 1), BLUE);
+        assert_eq!(*image.get_pixel(2, 2), Rgba([0, 0, 0, 255]));
+        assert_eq!(*image.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+    }
+}
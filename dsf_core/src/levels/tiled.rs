@@ -0,0 +1,915 @@
+//! Converts levels to and from maps made with the [Tiled](https://www.mapeditor.org) map editor.
+//!
+//! Only orthogonal, finite maps are supported, saved either as TMX with CSV or XML tile data, or
+//! as JSON. Each tile layer cell holds the anchor of a tile: the lower left cell of its footprint.
+//! Tiled draws tiles that are larger than a cell from the lower left as well, so multi-tile
+//! footprints look the same in both editors.
+//!
+//! Tiles that need per-instance data go in an object layer instead. These properties are
+//! understood on objects:
+//! - `tile`: the tile definition key, for objects that have no tile from the tileset.
+//! - `signal`: the name of the signal the tile is wired to.
+//! - `waypoints`: the route of a moving platform, as positions like `3,4 5,-2`.
+//!
+//! The origin of the world bounds is kept in the map properties `origin_x` and `origin_y`.
+//! Hidden layers and objects are left out of the level, like they are left out of the view in
+//! Tiled.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use xml::escape::escape_str_attribute;
+use xml::reader::{EventReader, XmlEvent};
+
+use crate::levels::level_save::LevelSave;
+use crate::levels::world_bounds::WorldBounds;
+use crate::systems::motion::structs::pos::Pos;
+
+/// Tiled stores whether a tile is flipped or rotated in the highest bits of its global tile ID.
+const FLIP_FLAGS: u32 = 0xF000_0000;
+
+/// Links the tiles of a Tiled tileset to tile definition keys.
+///
+/// The tileset itself is made in Tiled; this file only says which of its tiles is which.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TiledMapping {
+    /// The tileset that the mapping is for. This is matched against the file name of an external
+    /// tileset, or against the name of an embedded one.
+    pub tileset: String,
+    /// The size of a single cell in pixels, used to place objects in exported maps. Imported maps
+    /// use their own tile size.
+    pub tile_width: u32,
+    pub tile_height: u32,
+    /// Maps the IDs of tiles within the tileset to tile definition keys.
+    pub tiles: BTreeMap<u32, String>,
+}
+
+impl TiledMapping {
+    pub fn load_from_file(path: &Path) -> Result<Self, TiledError> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|error| TiledError::Parse(format!("{:?}: {}", path, error)))?;
+        ron::de::from_str(&data).map_err(|error| TiledError::Parse(error.to_string()))
+    }
+
+    /// The tile ID for the given key. If multiple IDs map to the same key, the lowest is used.
+    fn id(&self, key: &str) -> Option<u32> {
+        self.tiles
+            .iter()
+            .find(|(_, tile)| *tile == key)
+            .map(|(id, _)| *id)
+    }
+
+    fn is_tileset(&self, tileset: &TiledTileset) -> bool {
+        let file_name = tileset
+            .source
+            .as_ref()
+            .and_then(|source| Path::new(source).file_name())
+            .and_then(|name| name.to_str());
+        file_name == Some(self.tileset.as_str()) || tileset.name.as_ref() == Some(&self.tileset)
+    }
+}
+
+/// Reasons why a level cannot be converted to or from a Tiled map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TiledError {
+    /// The file is not valid TMX, JSON or RON.
+    Parse(String),
+    /// The map uses a Tiled feature that levels have no equivalent for.
+    Unsupported(String),
+    /// The map does not use the tileset from the mapping.
+    MissingTileset(String),
+    /// The map contains a tile that is not in the mapping.
+    UnknownTile(u32),
+    /// The level contains a tile that is not in the mapping.
+    NoTileId(String),
+    /// An object has a property that cannot be read, or has no tile.
+    InvalidObject(u32, String),
+    /// Two tiles are anchored at the same position.
+    Overlap(Pos),
+    /// A tile of the level lies outside its world bounds.
+    OutOfBounds(Pos),
+}
+
+impl fmt::Display for TiledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TiledError::Parse(message) => write!(f, "unable to parse: {}", message),
+            TiledError::Unsupported(feature) => write!(f, "not supported: {}", feature),
+            TiledError::MissingTileset(name) => write!(f, "the map has no tileset {:?}", name),
+            TiledError::UnknownTile(id) => write!(f, "tile {} is not in the mapping", id),
+            TiledError::NoTileId(key) => write!(f, "{:?} is not in the mapping", key),
+            TiledError::InvalidObject(id, message) => write!(f, "object {}: {}", id, message),
+            TiledError::Overlap(pos) => write!(f, "more than one tile at {:?}", pos),
+            TiledError::OutOfBounds(pos) => write!(f, "tile at {:?} is out of bounds", pos),
+        }
+    }
+}
+
+impl std::error::Error for TiledError {}
+
+/// A Tiled map, laid out the same as Tiled's JSON format.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TiledMap {
+    #[serde(default = "orthogonal")]
+    pub orientation: String,
+    #[serde(default)]
+    pub infinite: bool,
+    pub width: u32,
+    pub height: u32,
+    #[serde(rename = "tilewidth")]
+    pub tile_width: u32,
+    #[serde(rename = "tileheight")]
+    pub tile_height: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<TiledProperty>,
+    pub tilesets: Vec<TiledTileset>,
+    pub layers: Vec<TiledLayer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TiledTileset {
+    #[serde(rename = "firstgid")]
+    pub first_gid: u32,
+    /// The path of an external tileset file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// The name of an embedded tileset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum TiledLayer {
+    #[serde(rename = "tilelayer")]
+    Tiles(TileLayer),
+    #[serde(rename = "objectgroup")]
+    Objects(ObjectLayer),
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TileLayer {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    /// The global tile ID of every cell, row by row from the top left. Zero is an empty cell.
+    pub data: Vec<u32>,
+    #[serde(default = "visible")]
+    pub visible: bool,
+    #[serde(default = "opaque")]
+    pub opacity: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ObjectLayer {
+    pub name: String,
+    pub objects: Vec<TiledObject>,
+    #[serde(default = "visible")]
+    pub visible: bool,
+    #[serde(default = "opaque")]
+    pub opacity: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TiledObject {
+    pub id: u32,
+    /// The global tile ID, for objects that show a tile from a tileset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gid: Option<u32>,
+    /// The position in pixels. For tile objects, this is the lower left corner; for all other
+    /// objects, it is the upper left corner.
+    pub x: f32,
+    pub y: f32,
+    #[serde(default)]
+    pub width: f32,
+    #[serde(default)]
+    pub height: f32,
+    #[serde(default = "visible")]
+    pub visible: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<TiledProperty>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TiledProperty {
+    pub name: String,
+    #[serde(rename = "type", default = "string_type")]
+    pub kind: String,
+    pub value: Value,
+}
+
+impl TiledProperty {
+    fn int(name: &str, value: i32) -> Self {
+        TiledProperty {
+            name: name.to_string(),
+            kind: "int".to_string(),
+            value: Value::from(value),
+        }
+    }
+
+    fn string(name: &str, value: String) -> Self {
+        TiledProperty {
+            name: name.to_string(),
+            kind: string_type(),
+            value: Value::from(value),
+        }
+    }
+
+    /// The value as text, the way TMX files store it.
+    fn text(&self) -> String {
+        match &self.value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+fn orthogonal() -> String {
+    "orthogonal".to_string()
+}
+
+fn string_type() -> String {
+    "string".to_string()
+}
+
+/// Tiled shows layers and objects unless they are marked as hidden.
+fn visible() -> bool {
+    true
+}
+
+fn opaque() -> f32 {
+    1.
+}
+
+/// Reads a level from a Tiled map in TMX format.
+pub fn from_tmx(tmx: &str, mapping: &TiledMapping) -> Result<LevelSave, TiledError> {
+    level_from_tiled(&parse_tmx(tmx)?, mapping)
+}
+
+/// Reads a level from a Tiled map in JSON format.
+pub fn from_tiled_json(json: &str, mapping: &TiledMapping) -> Result<LevelSave, TiledError> {
+    let map = serde_json::from_str::<TiledMap>(json)
+        .map_err(|error| TiledError::Parse(error.to_string()))?;
+    level_from_tiled(&map, mapping)
+}
+
+/// Writes the level as a Tiled map in TMX format.
+pub fn to_tmx(level: &LevelSave, mapping: &TiledMapping) -> Result<String, TiledError> {
+    Ok(write_tmx(&level_to_tiled(level, mapping)?))
+}
+
+/// Writes the level as a Tiled map in JSON format.
+pub fn to_tiled_json(level: &LevelSave, mapping: &TiledMapping) -> Result<String, TiledError> {
+    serde_json::to_string_pretty(&level_to_tiled(level, mapping)?)
+        .map_err(|error| TiledError::Parse(error.to_string()))
+}
+
+/// Converts a Tiled map into a level.
+pub fn level_from_tiled(map: &TiledMap, mapping: &TiledMapping) -> Result<LevelSave, TiledError> {
+    if map.orientation != "orthogonal" {
+        return Err(TiledError::Unsupported(format!("{} maps", map.orientation)));
+    }
+    if map.infinite {
+        return Err(TiledError::Unsupported("infinite maps".to_string()));
+    }
+    if map.tile_width == 0 || map.tile_height == 0 {
+        return Err(TiledError::Parse("the map has no tile size".to_string()));
+    }
+    let first_gid = map
+        .tilesets
+        .iter()
+        .find(|tileset| mapping.is_tileset(tileset))
+        .ok_or_else(|| TiledError::MissingTileset(mapping.tileset.clone()))?
+        .first_gid;
+    let key = |gid: u32| {
+        (gid & !FLIP_FLAGS)
+            .checked_sub(first_gid)
+            .and_then(|id| mapping.tiles.get(&id))
+            .ok_or(TiledError::UnknownTile(gid & !FLIP_FLAGS))
+    };
+    let (width, height) = (map.width as i32, map.height as i32);
+    let origin = Pos::new(
+        map_property(map, "origin_x").unwrap_or(-width / 2),
+        map_property(map, "origin_y").unwrap_or(-height / 2),
+    );
+    let mut level = LevelSave {
+        world_bounds: WorldBounds::new(origin.x, origin.y, width, height),
+        ..LevelSave::default()
+    };
+    for layer in &map.layers {
+        match layer {
+            TiledLayer::Tiles(layer) if layer.visible => {
+                if layer.width == 0 || layer.data.len() != (layer.width * layer.height) as usize {
+                    return Err(TiledError::Parse(format!(
+                        "layer {:?} has {} cells, which does not fit {} by {}",
+                        layer.name,
+                        layer.data.len(),
+                        layer.width,
+                        layer.height
+                    )));
+                }
+                for (index, gid) in layer.data.iter().enumerate().filter(|(_, gid)| **gid != 0) {
+                    let column = index as i32 % layer.width as i32;
+                    let row = index as i32 / layer.width as i32;
+                    let pos = origin.append_xy(column, height - 1 - row);
+                    insert(&mut level, pos, key(*gid)?.clone())?;
+                }
+            }
+            TiledLayer::Objects(layer) if layer.visible => {
+                for object in layer.objects.iter().filter(|object| object.visible) {
+                    let invalid =
+                        |message: &str| TiledError::InvalidObject(object.id, message.into());
+                    let tile = match (object.gid, property(object, "tile")) {
+                        (_, Some(tile)) => tile.text(),
+                        (Some(gid), None) => key(gid)?.clone(),
+                        (None, None) => return Err(invalid("no tile")),
+                    };
+                    // Tile objects are positioned by their lower left corner, others by their top.
+                    let bottom = if object.gid.is_some() {
+                        object.y
+                    } else {
+                        object.y + object.height
+                    };
+                    let column = (object.x / map.tile_width as f32).round() as i32;
+                    let rows_from_top = (bottom / map.tile_height as f32).round() as i32;
+                    let pos = origin.append_xy(column, height - rows_from_top);
+                    insert(&mut level, pos, tile)?;
+                    if let Some(signal) = property(object, "signal") {
+                        level.wiring.insert(pos, signal.text());
+                    }
+                    if let Some(waypoints) = property(object, "waypoints") {
+                        let route = parse_waypoints(&waypoints.text())
+                            .ok_or_else(|| invalid("waypoints must look like 3,4 5,-2"))?;
+                        level.paths.insert(pos, route);
+                    }
+                }
+            }
+            TiledLayer::Tiles(_) | TiledLayer::Objects(_) => (),
+            TiledLayer::Unsupported => {
+                return Err(TiledError::Unsupported(
+                    "group and image layers".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(level)
+}
+
+/// Converts a level into a Tiled map. Tiles that are wired or have a path are put in an object
+/// layer, all other tiles in a single tile layer.
+pub fn level_to_tiled(level: &LevelSave, mapping: &TiledMapping) -> Result<TiledMap, TiledError> {
+    let bounds = &level.world_bounds;
+    let (width, height) = (bounds.width(), bounds.height());
+    let gid = |key: &String| {
+        mapping
+            .id(key)
+            .map(|id| id + 1)
+            .ok_or_else(|| TiledError::NoTileId(key.clone()))
+    };
+    let mut data = vec![0; (width * height) as usize];
+    let mut objects = Vec::new();
+    let ordered: BTreeMap<_, _> = level.tiles.iter().collect();
+    for (pos, key) in ordered {
+        let column = pos.x - bounds.x();
+        let row = height - 1 - (pos.y - bounds.y());
+        if !(0..width).contains(&column) || !(0..height).contains(&row) {
+            return Err(TiledError::OutOfBounds(*pos));
+        }
+        let signal = level.wiring.get(pos);
+        let waypoints = level.paths.get(pos);
+        if signal.is_none() && waypoints.is_none() {
+            data[(column + row * width) as usize] = gid(key)?;
+            continue;
+        }
+        let mut properties = Vec::new();
+        if let Some(signal) = signal {
+            properties.push(TiledProperty::string("signal", signal.clone()));
+        }
+        if let Some(waypoints) = waypoints {
+            properties.push(TiledProperty::string(
+                "waypoints",
+                format_waypoints(waypoints),
+            ));
+        }
+        objects.push(TiledObject {
+            id: objects.len() as u32 + 1,
+            gid: Some(gid(key)?),
+            x: (column as u32 * mapping.tile_width) as f32,
+            y: ((row + 1) as u32 * mapping.tile_height) as f32,
+            width: mapping.tile_width as f32,
+            height: mapping.tile_height as f32,
+            visible: true,
+            properties,
+        });
+    }
+    let mut layers = vec![TiledLayer::Tiles(TileLayer {
+        name: "tiles".to_string(),
+        width: width as u32,
+        height: height as u32,
+        data,
+        visible: true,
+        opacity: 1.,
+    })];
+    if !objects.is_empty() {
+        layers.push(TiledLayer::Objects(ObjectLayer {
+            name: "objects".to_string(),
+            objects,
+            visible: true,
+            opacity: 1.,
+        }));
+    }
+    Ok(TiledMap {
+        orientation: orthogonal(),
+        infinite: false,
+        width: width as u32,
+        height: height as u32,
+        tile_width: mapping.tile_width,
+        tile_height: mapping.tile_height,
+        properties: vec![
+            TiledProperty::int("origin_x", bounds.x()),
+            TiledProperty::int("origin_y", bounds.y()),
+        ],
+        tilesets: vec![TiledTileset {
+            first_gid: 1,
+            source: Some(mapping.tileset.clone()),
+            name: None,
+        }],
+        layers,
+    })
+}
+
+fn insert(level: &mut LevelSave, pos: Pos, key: String) -> Result<(), TiledError> {
+    if level.tiles.insert(pos, key).is_some() {
+        return Err(TiledError::Overlap(pos));
+    }
+    Ok(())
+}
+
+fn property<'a>(object: &'a TiledObject, name: &str) -> Option<&'a TiledProperty> {
+    object
+        .properties
+        .iter()
+        .find(|property| property.name == name)
+}
+
+fn map_property(map: &TiledMap, name: &str) -> Option<i32> {
+    map.properties
+        .iter()
+        .find(|property| property.name == name)
+        .and_then(|property| property.text().parse().ok())
+}
+
+fn parse_waypoints(text: &str) -> Option<Vec<Pos>> {
+    text.split_whitespace()
+        .map(|pair| {
+            let (x, y) = pair.split_once(',')?;
+            Some(Pos::new(x.parse().ok()?, y.parse().ok()?))
+        })
+        .collect()
+}
+
+fn format_waypoints(waypoints: &[Pos]) -> String {
+    waypoints
+        .iter()
+        .map(|pos| format!("{},{}", pos.x, pos.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads the parts of a TMX file that levels use into the same structure as Tiled's JSON format.
+fn parse_tmx(tmx: &str) -> Result<TiledMap, TiledError> {
+    let mut map: Option<TiledMap> = None;
+    // The element whose properties are being read: the map itself, or the last object.
+    let mut in_object = false;
+    let mut in_data = false;
+    let mut csv: Option<String> = None;
+    for event in EventReader::from_str(tmx) {
+        let event = event.map_err(|error| TiledError::Parse(error.to_string()))?;
+        match event {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let attribute = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|attribute| attribute.name.local_name == key)
+                        .map(|attribute| attribute.value.clone())
+                };
+                let visible = attribute("visible").as_deref() != Some("0");
+                let number = |key: &str| -> Result<f32, TiledError> {
+                    attribute(key).map_or(Ok(0.), |value| {
+                        value
+                            .parse()
+                            .map_err(|_| TiledError::Parse(format!("{} is not a number", key)))
+                    })
+                };
+                if name.local_name == "map" {
+                    map = Some(TiledMap {
+                        orientation: attribute("orientation").unwrap_or_else(orthogonal),
+                        infinite: attribute("infinite").as_deref() == Some("1"),
+                        width: number("width")? as u32,
+                        height: number("height")? as u32,
+                        tile_width: number("tilewidth")? as u32,
+                        tile_height: number("tileheight")? as u32,
+                        properties: Vec::new(),
+                        tilesets: Vec::new(),
+                        layers: Vec::new(),
+                    });
+                    continue;
+                }
+                let map = map
+                    .as_mut()
+                    .ok_or_else(|| TiledError::Parse("no map element".to_string()))?;
+                match name.local_name.as_str() {
+                    "tileset" => map.tilesets.push(TiledTileset {
+                        first_gid: number("firstgid")? as u32,
+                        source: attribute("source"),
+                        name: attribute("name"),
+                    }),
+                    "layer" => map.layers.push(TiledLayer::Tiles(TileLayer {
+                        name: attribute("name").unwrap_or_default(),
+                        width: number("width")? as u32,
+                        height: number("height")? as u32,
+                        data: Vec::new(),
+                        visible,
+                        opacity: 1.,
+                    })),
+                    "data" => match attribute("encoding").as_deref() {
+                        Some("csv") => csv = Some(String::new()),
+                        None => in_data = true,
+                        Some(encoding) => {
+                            return Err(TiledError::Unsupported(format!("{} encoding", encoding)))
+                        }
+                    },
+                    "chunk" => return Err(TiledError::Unsupported("infinite maps".to_string())),
+                    // Embedded tilesets have tile elements too, only those in the data are cells.
+                    "tile" if in_data => {
+                        if let Some(TiledLayer::Tiles(layer)) = map.layers.last_mut() {
+                            layer.data.push(number("gid")? as u32);
+                        }
+                    }
+                    "objectgroup" => map.layers.push(TiledLayer::Objects(ObjectLayer {
+                        name: attribute("name").unwrap_or_default(),
+                        objects: Vec::new(),
+                        visible,
+                        opacity: 1.,
+                    })),
+                    "object" => {
+                        in_object = true;
+                        if let Some(TiledLayer::Objects(layer)) = map.layers.last_mut() {
+                            layer.objects.push(TiledObject {
+                                id: number("id")? as u32,
+                                gid: attribute("gid").and_then(|gid| gid.parse().ok()),
+                                x: number("x")?,
+                                y: number("y")?,
+                                width: number("width")?,
+                                height: number("height")?,
+                                visible,
+                                properties: Vec::new(),
+                            });
+                        }
+                    }
+                    "property" => {
+                        let property = TiledProperty {
+                            name: attribute("name").unwrap_or_default(),
+                            kind: attribute("type").unwrap_or_else(string_type),
+                            value: Value::from(attribute("value").unwrap_or_default()),
+                        };
+                        let object = match map.layers.last_mut() {
+                            Some(TiledLayer::Objects(layer)) if in_object => {
+                                layer.objects.last_mut()
+                            }
+                            _ => None,
+                        };
+                        match object {
+                            Some(object) => object.properties.push(property),
+                            None => map.properties.push(property),
+                        }
+                    }
+                    "imagelayer" | "group" => {
+                        map.layers.push(TiledLayer::Unsupported);
+                    }
+                    _ => (),
+                }
+            }
+            XmlEvent::Characters(text) => {
+                if let Some(csv) = csv.as_mut() {
+                    csv.push_str(&text);
+                }
+            }
+            XmlEvent::EndElement { name } => match name.local_name.as_str() {
+                "object" => in_object = false,
+                "data" => {
+                    in_data = false;
+                    if let (Some(text), Some(TiledLayer::Tiles(layer))) = (
+                        csv.take(),
+                        map.as_mut().and_then(|map| map.layers.last_mut()),
+                    ) {
+                        layer.data = text
+                            .split(',')
+                            .map(|gid| gid.trim().parse::<u32>())
+                            .collect::<Result<_, _>>()
+                            .map_err(|error| TiledError::Parse(error.to_string()))?;
+                    }
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+    map.ok_or_else(|| TiledError::Parse("no map element".to_string()))
+}
+
+/// Writes a map in TMX format, with CSV tile data.
+fn write_tmx(map: &TiledMap) -> String {
+    let mut tmx = String::new();
+    let mut line = |text: String| {
+        tmx.push_str(&text);
+        tmx.push('\n');
+    };
+    line(r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string());
+    line(format!(
+        r#"<map version="1.8" orientation="{}" renderorder="right-down" width="{}" height="{}" tilewidth="{}" tileheight="{}" infinite="0">"#,
+        map.orientation, map.width, map.height, map.tile_width, map.tile_height
+    ));
+    write_properties(&mut line, " ", &map.properties);
+    for tileset in &map.tilesets {
+        let mut attributes = format!(r#"firstgid="{}""#, tileset.first_gid);
+        if let Some(source) = &tileset.source {
+            let _ = write!(attributes, r#" source="{}""#, escape_str_attribute(source));
+        }
+        if let Some(name) = &tileset.name {
+            let _ = write!(attributes, r#" name="{}""#, escape_str_attribute(name));
+        }
+        line(format!(" <tileset {}/>", attributes));
+    }
+    for (index, layer) in map.layers.iter().enumerate() {
+        match layer {
+            TiledLayer::Tiles(layer) => {
+                line(format!(
+                    r#" <layer id="{}" name="{}" width="{}" height="{}">"#,
+                    index + 1,
+                    escape_str_attribute(&layer.name),
+                    layer.width,
+                    layer.height
+                ));
+                line(r#"  <data encoding="csv">"#.to_string());
+                let rows = layer
+                    .data
+                    .chunks(layer.width.max(1) as usize)
+                    .map(|row| row.iter().map(u32::to_string).collect::<Vec<_>>().join(","))
+                    .collect::<Vec<_>>();
+                line(rows.join(",\n"));
+                line("  </data>".to_string());
+                line(" </layer>".to_string());
+            }
+            TiledLayer::Objects(layer) => {
+                line(format!(
+                    r#" <objectgroup id="{}" name="{}">"#,
+                    index + 1,
+                    escape_str_attribute(&layer.name)
+                ));
+                for object in &layer.objects {
+                    let gid = object
+                        .gid
+                        .map(|gid| format!(r#" gid="{}""#, gid))
+                        .unwrap_or_default();
+                    let attributes = format!(
+                        r#"id="{}"{} x="{}" y="{}" width="{}" height="{}""#,
+                        object.id, gid, object.x, object.y, object.width, object.height
+                    );
+                    if object.properties.is_empty() {
+                        line(format!("  <object {}/>", attributes));
+                    } else {
+                        line(format!("  <object {}>", attributes));
+                        write_properties(&mut line, "   ", &object.properties);
+                        line("  </object>".to_string());
+                    }
+                }
+                line(" </objectgroup>".to_string());
+            }
+            TiledLayer::Unsupported => (),
+        }
+    }
+    line("</map>".to_string());
+    tmx
+}
+
+fn write_properties(line: &mut impl FnMut(String), indent: &str, properties: &[TiledProperty]) {
+    if properties.is_empty() {
+        return;
+    }
+    line(format!("{}<properties>", indent));
+    for property in properties {
+        let kind = if property.kind == "string" {
+            String::new()
+        } else {
+            format!(r#" type="{}""#, escape_str_attribute(&property.kind))
+        };
+        line(format!(
+            r#"{} <property name="{}"{} value="{}"/>"#,
+            indent,
+            escape_str_attribute(&property.name),
+            kind,
+            escape_str_attribute(&property.text())
+        ));
+    }
+    line(format!("{}</properties>", indent));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> TiledMapping {
+        TiledMapping {
+            tileset: "dsf.tsx".to_string(),
+            tile_width: 16,
+            tile_height: 16,
+            tiles: BTreeMap::from([
+                (0, "Block1".to_string()),
+                (1, "Player".to_string()),
+                (2, "Platform".to_string()),
+                (3, "Lever".to_string()),
+            ]),
+        }
+    }
+
+    fn level() -> LevelSave {
+        let mut level = LevelSave {
+            world_bounds: WorldBounds::new(-2, 3, 4, 3),
+            ..LevelSave::default()
+        };
+        for x in -2..2 {
+            level.tiles.insert(Pos::new(x, 3), "Block1".to_string());
+        }
+        level.tiles.insert(Pos::new(-2, 4), "Player".to_string());
+        level.tiles.insert(Pos::new(0, 5), "Platform".to_string());
+        level.tiles.insert(Pos::new(1, 4), "Lever".to_string());
+        level
+            .paths
+            .insert(Pos::new(0, 5), vec![Pos::new(3, 5), Pos::new(3, -1)]);
+        level.wiring.insert(Pos::new(1, 4), "door".to_string());
+        level
+    }
+
+    fn assert_same(actual: &LevelSave, expected: &LevelSave) {
+        assert_eq!(actual.world_bounds.pos, expected.world_bounds.pos);
+        assert_eq!(actual.world_bounds.dimens, expected.world_bounds.dimens);
+        assert_eq!(actual.tiles, expected.tiles);
+        assert_eq!(actual.wiring, expected.wiring);
+        assert_eq!(actual.paths, expected.paths);
+    }
+
+    #[test]
+    fn shipped_mapping_covers_all_tiles() {
+        let mapping: TiledMapping =
+            ron::de::from_str(include_str!("../../../assets/world/tiled_mapping.ron")).unwrap();
+        let tile_defs: crate::levels::tiles::tile_defs::TileDefinitions =
+            ron::de::from_str(include_str!("../../../assets/world/tile_definitions.ron")).unwrap();
+        for key in tile_defs.map.keys() {
+            assert!(mapping.id(key).is_some(), "{} is not in the mapping", key);
+        }
+        for key in mapping.tiles.values() {
+            assert!(tile_defs.map.contains_key(key), "{} is not a tile", key);
+        }
+    }
+
+    #[test]
+    fn round_trips_through_tmx() {
+        let tmx = to_tmx(&level(), &mapping()).unwrap();
+        assert_same(&from_tmx(&tmx, &mapping()).unwrap(), &level());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let json = to_tiled_json(&level(), &mapping()).unwrap();
+        assert_same(&from_tiled_json(&json, &mapping()).unwrap(), &level());
+    }
+
+    #[test]
+    fn reads_tmx_made_in_tiled() {
+        let tmx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.8" tiledversion="1.8.2" orientation="orthogonal" renderorder="right-down" width="3" height="2" tilewidth="16" tileheight="16" infinite="0" nextlayerid="3" nextobjectid="2">
+ <tileset firstgid="1" source="other.tsx"/>
+ <tileset firstgid="11" source="tilesets/dsf.tsx"/>
+ <layer id="1" name="Tile Layer 1" width="3" height="2">
+  <data encoding="csv">
+0,0,12,
+11,2147483659,11
+</data>
+ </layer>
+ <objectgroup id="2" name="Object Layer 1">
+  <object id="1" x="16" y="0" width="16" height="16">
+   <properties>
+    <property name="tile" value="Lever"/>
+    <property name="signal" value="bridge"/>
+   </properties>
+  </object>
+  <object id="2" gid="12" x="0" y="16" width="16" height="16" visible="0"/>
+ </objectgroup>
+ <layer id="3" name="Hidden" width="3" height="2" visible="0">
+  <data encoding="csv">
+12,0,0,
+0,0,0
+</data>
+ </layer>
+</map>"#;
+        let level = from_tmx(tmx, &mapping()).unwrap();
+        assert_eq!(level.world_bounds.pos, Pos::new(-1, -1));
+        let tiles = level
+            .tiles
+            .iter()
+            .map(|(pos, key)| (*pos, key.as_str()))
+            .collect::<BTreeMap<_, _>>();
+        let expected = BTreeMap::from([
+            (Pos::new(-1, -1), "Block1"),
+            (Pos::new(0, -1), "Block1"),
+            (Pos::new(1, -1), "Block1"),
+            (Pos::new(1, 0), "Player"),
+            (Pos::new(0, 0), "Lever"),
+        ]);
+        assert_eq!(tiles, expected);
+        assert_eq!(level.wiring.get(&Pos::new(0, 0)).unwrap(), "bridge");
+    }
+
+    #[test]
+    fn places_objects_by_the_tile_size_of_the_map() {
+        let tmx = r#"<map orientation="orthogonal" width="3" height="2" tilewidth="32" tileheight="32">
+ <tileset firstgid="1" source="dsf.tsx"/>
+ <objectgroup name="objects">
+  <object id="1" x="64" y="0" width="32" height="32">
+   <properties>
+    <property name="tile" value="Lever"/>
+    <property name="signal" value="bridge"/>
+   </properties>
+  </object>
+  <object id="2" gid="3" x="0" y="64" width="96" height="32">
+   <properties>
+    <property name="waypoints" value="1,0"/>
+   </properties>
+  </object>
+ </objectgroup>
+</map>"#;
+        let level = from_tmx(tmx, &mapping()).unwrap();
+        assert_eq!(
+            level.tiles.get(&Pos::new(1, 0)).map(String::as_str),
+            Some("Lever")
+        );
+        assert_eq!(level.wiring.get(&Pos::new(1, 0)).unwrap(), "bridge");
+        assert_eq!(
+            level.tiles.get(&Pos::new(-1, -1)).map(String::as_str),
+            Some("Platform")
+        );
+        assert_eq!(
+            level.paths.get(&Pos::new(-1, -1)),
+            Some(&vec![Pos::new(1, 0)])
+        );
+    }
+
+    #[test]
+    fn rejects_what_levels_cannot_hold() {
+        let tmx = |tileset: &str, data: &str| {
+            format!(
+                r#"<map orientation="orthogonal" width="2" height="1" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" source="{}"/>
+ <layer name="tiles" width="2" height="1"><data encoding="csv">{}</data></layer>
+</map>"#,
+                tileset, data
+            )
+        };
+        assert_eq!(
+            from_tmx(&tmx("other.tsx", "1,0"), &mapping()).err(),
+            Some(TiledError::MissingTileset("dsf.tsx".to_string()))
+        );
+        assert_eq!(
+            from_tmx(&tmx("dsf.tsx", "9,0"), &mapping()).err(),
+            Some(TiledError::UnknownTile(9))
+        );
+        assert!(matches!(
+            from_tmx(&tmx("dsf.tsx", "1,0,0"), &mapping()),
+            Err(TiledError::Parse(_))
+        ));
+        let no_width =
+            tmx("dsf.tsx", "1,0").replace(r#"name="tiles" width="2""#, r#"name="tiles""#);
+        assert!(matches!(
+            from_tmx(&no_width, &mapping()),
+            Err(TiledError::Parse(_))
+        ));
+        let mut unmapped = level();
+        unmapped
+            .tiles
+            .insert(Pos::new(1, 5), "ExtraLife".to_string());
+        assert_eq!(
+            to_tmx(&unmapped, &mapping()).err(),
+            Some(TiledError::NoTileId("ExtraLife".to_string()))
+        );
+    }
+}
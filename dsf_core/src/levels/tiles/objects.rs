@@ -1,29 +1,73 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::levels::tiles::tile_defs::ToolType;
+use crate::levels::tiles::tile_defs::{Durability, ToolDefinition, ToolType};
 use crate::loading::assets::SpriteType;
 use crate::systems::motion::structs::pos::Pos;
 
+/// The key of the tile definition that an entity was spawned from. This is what allows the current
+/// state of a level to be written back into a `LevelSave`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Component)]
+pub struct LevelTile(pub String);
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Component)]
 pub struct Key;
 
+/// A treasure that can be picked up for points. Collectibles are optional; they are not needed to
+/// finish the level.
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Component)]
+pub struct Collectible {
+    pub score: u32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Component)]
 pub struct Tool {
     pub tool_type: ToolType,
+    pub durability: Durability,
+    /// How often the tool was used so far.
+    pub times_used: u32,
     pub sprite: SpriteType,
     pub sprite_nr: usize,
 }
 
 impl Tool {
     #[must_use]
-    pub fn new(tool_type: ToolType, sprite: SpriteType, sprite_nr: usize) -> Self {
+    pub fn new(definition: ToolDefinition, sprite: SpriteType, sprite_nr: usize) -> Self {
         Tool {
-            tool_type,
+            tool_type: definition.tool_type,
+            durability: definition.durability,
+            times_used: 0,
             sprite,
             sprite_nr,
         }
     }
+
+    /// Wears the tool down by one use. Returns true if that was the last use and the tool broke.
+    pub fn use_once(&mut self) -> bool {
+        self.times_used += 1;
+        self.remaining_uses() == Some(0)
+    }
+
+    /// How often the tool can still be used before it breaks, or None if it never breaks.
+    #[must_use]
+    pub fn remaining_uses(&self) -> Option<u32> {
+        match self.durability {
+            Durability::Uses(uses) => Some(uses.saturating_sub(self.times_used)),
+            Durability::Infinite => None,
+        }
+    }
+
+    /// The fraction of uses that are left, or None if the tool never breaks or is single-use.
+    /// Only tools with multiple uses need to show how worn they are.
+    #[must_use]
+    pub fn condition(&self) -> Option<f32> {
+        match self.durability {
+            Durability::Uses(uses) if uses > 1 => self
+                .remaining_uses()
+                .map(|remaining| remaining as f32 / uses as f32),
+            _ => None,
+        }
+    }
 }
 
 /// A miniature version of every key is found on the exit door.
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::loading::assets::{AssetType, SpriteType};
 use crate::systems::motion::structs::dimens::Dimens;
-use crate::systems::motion::structs::direction::Direction1D;
+use crate::systems::motion::structs::direction::{CardinalDirection, Direction1D};
 
 /// This resource stores tile definitions. It is used in both the level editor and the actual game.
 /// Definitions are loaded from a file. Each tile definition describes the properties of a type of
@@ -73,11 +73,16 @@ pub struct TileDefinition {
     /// representations of any tile, so it can be displayed in the editor GUI.
     // TODO: Remove Option, because this should always be present?
     pub preview_asset: Option<AssetType>,
+    /// Optional RGB color that is multiplied into the sprite. This allows several tiles to share
+    /// the same texture while still being distinguishable.
+    pub tint: Option<(f32, f32, f32)>,
     /// Use this if there are any special components or child-entities that should be attached to
     /// this tile.
     pub archetype: Option<Archetype>,
     /// If and under what circumstances this block can be destroyed.
     pub sturdiness: Sturdiness,
+    /// If set, touching this tile is deadly.
+    pub hazard: Option<HazardType>,
 }
 
 impl TileDefinition {
@@ -93,8 +98,10 @@ impl TileDefinition {
             collision: None,
             asset: Some(AssetType(SpriteType::NotFound, 0)),
             preview_asset: None,
+            tint: None,
             archetype: None,
             sturdiness: Sturdiness::Invulnerable,
+            hazard: None,
         }
     }
 
@@ -131,11 +138,60 @@ impl TileDefinition {
     }
 
     pub fn is_trapped(&self) -> bool {
-        self.archetype == Some(Archetype::TrapWall)
+        self.trap().is_some()
+    }
+
+    /// Returns the trap definition if this tile is part of a trap wall.
+    pub fn trap(&self) -> Option<&TrapDefinition> {
+        if let Some(Archetype::TrapWall(trap)) = &self.archetype {
+            Some(trap)
+        } else {
+            None
+        }
     }
 
     pub fn is_revolving(&self) -> bool {
-        matches!(self.archetype, Some(Archetype::RevolvingDoor(_)))
+        self.revolving().is_some()
+    }
+
+    /// Returns the revolving door definition if this tile is a revolving door fragment.
+    pub fn revolving(&self) -> Option<&RevolvingDoorDefinition> {
+        if let Some(Archetype::RevolvingDoor(door)) = &self.archetype {
+            Some(door)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the platform definition if this tile is a moving platform or elevator.
+    pub fn platform(&self) -> Option<&PlatformDefinition> {
+        if let Some(Archetype::Platform(platform)) = &self.archetype {
+            Some(platform)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the color the sprite of this tile should be drawn with.
+    /// Colored keys and locked doors are drawn in their key color, unless they have a tint.
+    pub fn color(&self) -> Color {
+        match (self.tint, &self.archetype) {
+            (Some((red, green, blue)), _) => Color::rgb(red, green, blue),
+            (None, Some(Archetype::ColoredKey(color) | Archetype::LockedDoor(color))) => {
+                color.color()
+            }
+            _ => Color::WHITE,
+        }
+    }
+
+    /// Returns the mechanism definition if this tile reacts to signals by switching to another
+    /// tile.
+    pub fn mechanism(&self) -> Option<&MechanismDefinition> {
+        if let Some(Archetype::Mechanism(mechanism)) = &self.archetype {
+            Some(mechanism)
+        } else {
+            None
+        }
     }
 
     /// This returns the preview asset if it exists and the standard asset otherwise.
@@ -208,10 +264,223 @@ pub enum Archetype {
     /// After collecting all keys, finish level by reaching this door.
     Door,
     /// An item that can be picked up and equipped by the player.
-    Tool(ToolType),
-    Spawner,
-    TrapWall,
-    RevolvingDoor(Direction1D),
+    Tool(ToolDefinition),
+    /// Periodically spawns an enemy.
+    Spawner(SpawnerDefinition),
+    /// A segment of a trap wall. Once triggered, the trap turns into solid tiles one segment at a
+    /// time.
+    TrapWall(TrapDefinition),
+    /// A fragment of a revolving door. Characters can pass through it in one direction only,
+    /// after which the door turns around.
+    RevolvingDoor(RevolvingDoorDefinition),
+    /// Emits a signal while it is active. Which signal it emits is configured per level, in the
+    /// level's wiring.
+    Trigger(TriggerType),
+    /// Switches to a different tile whenever the signal it is wired to changes. Doors that open
+    /// and bridges that extend are both built out of a pair of these.
+    Mechanism(MechanismDefinition),
+    /// A solid tile that travels along a path of waypoints, carrying any characters standing on
+    /// it. The path itself is configured per level. Elevators are platforms with a vertical path.
+    Platform(PlatformDefinition),
+    /// A floor that crumbles away some time after the player first stands on it.
+    CrumblingFloor(CrumblingFloorDefinition),
+    /// A block that falls down as soon as there is nothing beneath it, crushing any character it
+    /// lands on.
+    FallingBlock,
+    /// A colored key. Unlike the level keys, colored keys are not needed to finish the level. They
+    /// are carried by the player until they are used to open a locked door of the same color.
+    ColoredKey(KeyColor),
+    /// A door that stays shut until the player touches it while carrying a key of the same color.
+    /// Opening the door uses up the key.
+    LockedDoor(KeyColor),
+    /// A treasure that is not needed to finish the level, but adds the given value to the
+    /// player's score.
+    Collectible(u32),
+    /// Grants the player an extra life. Only has an effect if lives are limited.
+    ExtraLife,
+    /// Saves the player's progress through the level when touched. When the player dies, they
+    /// return to the last checkpoint they touched, rather than to the start of the level.
+    Checkpoint,
+}
+
+/// Describes the enemies that a spawner spawns.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct SpawnerDefinition {
+    /// The width and height of the spawned enemies. They appear at the bottom of the spawner,
+    /// centered horizontally.
+    pub enemy_dimens: Dimens,
+}
+
+impl Default for SpawnerDefinition {
+    fn default() -> Self {
+        SpawnerDefinition {
+            enemy_dimens: Dimens::new(2, 2),
+        }
+    }
+}
+
+/// Describes what a trap wall turns into once it is triggered, and how fast.
+///
+/// A trap consists of a line of adjacent trapped tiles. When a character steps into any of them,
+/// the segment at the start of the line is triggered. After the delay, that segment turns into the
+/// result tile and the next segment in the growth direction is triggered, and so on.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct TrapDefinition {
+    /// Key of the tile definition that each segment turns into.
+    pub result: String,
+    /// Time in seconds between a segment being triggered and it turning into the result tile.
+    pub delay: f32,
+    /// The direction in which the trap grows, starting from the segment furthest against this
+    /// direction.
+    pub direction: CardinalDirection,
+}
+
+impl Default for TrapDefinition {
+    fn default() -> Self {
+        TrapDefinition {
+            result: "Block2".to_string(),
+            delay: 1.,
+            direction: CardinalDirection::Down,
+        }
+    }
+}
+
+/// Describes a revolving door fragment and the tile definitions it switches between.
+///
+/// A revolving door is never turned into a different kind of tile, it only switches between the
+/// variants listed here. That way each variant can have its own collision data.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct RevolvingDoorDefinition {
+    /// The direction a character would be facing if they used the door.
+    pub facing: Direction1D,
+    /// Key of the colliding variant of this door that lets characters through in the positive
+    /// direction.
+    pub facing_positive: String,
+    /// Key of the colliding variant of this door that lets characters through in the negative
+    /// direction.
+    pub facing_negative: String,
+    /// Key of the non-colliding variant that is used while the door is turning.
+    pub turning: String,
+}
+
+impl RevolvingDoorDefinition {
+    /// Returns the key of the colliding variant of this door for the given facing.
+    #[must_use]
+    pub fn key_for(&self, facing: Direction1D) -> &str {
+        if facing.is_negative() {
+            &self.facing_negative
+        } else {
+            &self.facing_positive
+        }
+    }
+}
+
+impl Default for RevolvingDoorDefinition {
+    fn default() -> Self {
+        RevolvingDoorDefinition {
+            facing: Direction1D::Positive,
+            facing_positive: "RevolvingDoorFacingPositive".to_string(),
+            facing_negative: "RevolvingDoorFacingNegative".to_string(),
+            turning: "RevolvingDoorNonColliding".to_string(),
+        }
+    }
+}
+
+/// The different ways a trigger can be activated.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub enum TriggerType {
+    /// Flips between on and off every time the player steps onto it.
+    Lever,
+    /// Is on for as long as the player or an enemy is standing on it.
+    PressurePlate,
+    /// Switches on once the player has collected at least this many keys.
+    KeysCollected(usize),
+}
+
+/// One half of a pair of tiles that switch into each other when their signal changes.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MechanismDefinition {
+    /// True if this is the variant that should be present while the signal is on.
+    pub powered: bool,
+    /// Key of the tile definition that this tile switches to when the signal changes.
+    /// Both tiles should have the same dimensions.
+    pub counterpart: String,
+}
+
+/// Describes how long a crumbling floor holds once the player stands on it.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct CrumblingFloorDefinition {
+    /// Time in seconds between the player first standing on the floor and the floor crumbling.
+    pub delay: f32,
+}
+
+impl Default for CrumblingFloorDefinition {
+    fn default() -> Self {
+        CrumblingFloorDefinition { delay: 1. }
+    }
+}
+
+/// Describes how fast a moving platform travels along its path.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct PlatformDefinition {
+    /// How many tiles the platform moves per second.
+    pub speed: f32,
+    /// Time in seconds that the platform waits at each waypoint before moving on.
+    pub pause: f32,
+}
+
+impl Default for PlatformDefinition {
+    fn default() -> Self {
+        PlatformDefinition {
+            speed: 2.,
+            pause: 0.5,
+        }
+    }
+}
+
+/// The colors that keys and locked doors come in. A key only opens doors of its own color.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub enum KeyColor {
+    Red,
+    Green,
+    Blue,
+    Yellow,
+}
+
+impl KeyColor {
+    /// The color used to draw keys and doors of this color.
+    #[must_use]
+    pub fn color(&self) -> Color {
+        match self {
+            KeyColor::Red => Color::rgb(1.0, 0.3, 0.3),
+            KeyColor::Green => Color::rgb(0.3, 1.0, 0.3),
+            KeyColor::Blue => Color::rgb(0.4, 0.5, 1.0),
+            KeyColor::Yellow => Color::rgb(1.0, 1.0, 0.3),
+        }
+    }
+}
+
+/// The different kinds of deadly tiles. Any character that touches a hazard dies, the type only
+/// determines how.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum HazardType {
+    Spikes,
+    Fire,
+    /// Squashes characters flat. Crushers are usually moving platforms, which do not stop for
+    /// characters that are in their way.
+    Crusher,
 }
 
 /// What it takes to break this block.
@@ -231,7 +500,32 @@ impl Default for Sturdiness {
     }
 }
 
-#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq)]
+/// Describes what a tool does and how often it can be used before it breaks.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ToolDefinition {
+    pub tool_type: ToolType,
+    #[serde(default)]
+    pub durability: Durability,
+}
+
+/// How often a tool can be used before it breaks.
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub enum Durability {
+    /// The tool breaks after it was used this many times.
+    Uses(u32),
+    /// The tool never breaks.
+    Infinite,
+}
+
+impl Default for Durability {
+    fn default() -> Self {
+        Durability::Uses(1)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub enum ToolType {
     /// This tool will break the blocks that the player is facing, n layers deep.
@@ -246,6 +540,46 @@ pub enum ToolType {
     /// If the player is facing right and occupies blocks (0, 0) to (1, 1) inclusive, the blocks
     /// that are targeted are: (1, -1) to (2, -depth) inclusive.
     BreakBlocksBelow(u8),
+    /// This tool builds a column of tiles (usually ladders) upward from the player's feet, in the
+    /// columns that the player occupies. The depth is how many tiles high the column is.
+    ///
+    /// If the player occupies blocks (0, 0) to (1, 1) inclusive and the tile is a 2x1 ladder,
+    /// ladders are placed at (0, 0) to (0, depth - 1) inclusive.
+    PlaceLadder(PlacementDefinition),
+    /// This tool builds a bridge across a gap: a row of tiles at the height of the floor the
+    /// player is standing on, in the direction the player is facing. The depth is how many tiles
+    /// long the bridge is.
+    ///
+    /// If the player is facing right and occupies blocks (0, 0) to (1, 1) inclusive, the tiles
+    /// are placed at: (2, -1) to (1 + depth, -1) inclusive.
+    PlaceBridge(PlacementDefinition),
+    /// This tool places blocks in front of the player, n layers deep. It targets exactly the same
+    /// blocks as `BreakBlocksHorizontally`.
+    PlaceBlocks(PlacementDefinition),
+}
+
+/// Describes what a tool that adds terrain places and how much of it.
+/// Placing only succeeds if all targeted positions are empty.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct PlacementDefinition {
+    /// Key of the tile definition that is placed.
+    pub tile: String,
+    /// How many tiles are placed. What this means exactly depends on the type of tool.
+    pub depth: u8,
+    /// If set, the placed tiles disappear again after this many seconds.
+    pub lifetime: Option<f32>,
+}
+
+impl Default for PlacementDefinition {
+    fn default() -> Self {
+        PlacementDefinition {
+            tile: "Block2".to_string(),
+            depth: 1,
+            lifetime: None,
+        }
+    }
 }
 
 impl Default for ToolType {
@@ -109,7 +109,8 @@ impl TileMap {
             let dimens = tile_def.dimens;
             (0..dimens.x).for_each(|x| {
                 (0..dimens.y).for_each(|y| {
-                    self.tiles.remove(&actual_pos.append_xy(x, y));
+                    let pos = self.wrapped(&actual_pos.append_xy(x, y));
+                    self.tiles.remove(&pos);
                 });
             });
         }
@@ -124,7 +125,7 @@ impl TileMap {
     #[must_use]
     pub fn get_actual_pos(&self, pos: &Pos) -> Option<Pos> {
         let wrapped_pos = self.wrapped(pos);
-        match self.tiles.get(pos) {
+        match self.tiles.get(&wrapped_pos) {
             Some(Tile::TileDefKey(_)) => Some(wrapped_pos),
             Some(Tile::Dummy(anchor_pos)) => Some(*anchor_pos),
             _ => None,
@@ -138,13 +139,23 @@ impl TileMap {
         (0..dimens.x).for_each(|x| {
             (0..dimens.y).for_each(|y| {
                 if x != 0 || y != 0 {
-                    self.tiles
-                        .insert(wrapped_pos.append_xy(x, y), Tile::Dummy(wrapped_pos));
+                    // Tiles that stick out over the edge of the world wrap around to the other side.
+                    let dummy_pos = self.wrapped(&wrapped_pos.append_xy(x, y));
+                    self.tiles.insert(dummy_pos, Tile::Dummy(wrapped_pos));
                 }
             })
         })
     }
 
+    /// Returns the key of the tile definition whose anchor is at exactly the given position.
+    #[must_use]
+    pub fn get_tile_def_key(&self, pos: &Pos) -> Option<&String> {
+        match self.tiles.get(&self.wrapped(pos)) {
+            Some(Tile::TileDefKey(key)) => Some(key),
+            _ => None,
+        }
+    }
+
     /// Delegate function
     fn wrapped(&self, pos: &Pos) -> Pos {
         self.world_bounds.wrapped(pos)
@@ -0,0 +1,172 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::levels::level_save::LevelSave;
+use crate::levels::tiles::tile_defs::TileDefinitions;
+use crate::systems::motion::structs::pos::Pos;
+
+/// Something that is wrong with a level. Levels with problems may not load, or may not be
+/// playable the way they were meant to be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelProblem {
+    /// The tile at this position refers to a tile definition that does not exist.
+    UnknownTile(Pos, String),
+    /// The footprint of the tile at this position sticks out of the world bounds.
+    OutOfBounds(Pos, String),
+    /// The footprints of the tiles at these two positions share the given cell.
+    Overlap(Pos, Pos, Pos),
+    /// A unique tile appears more than once.
+    NotUnique(String, usize),
+    /// A mandatory tile does not appear in the level.
+    Missing(String),
+    /// There is wiring at this position, but no tile.
+    StrayWire(Pos),
+    /// There is a path at this position, but no moving platform.
+    StrayPath(Pos),
+}
+
+impl fmt::Display for LevelProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelProblem::UnknownTile(pos, key) => {
+                write!(f, "no tile definition for {:?} at {:?}", key, pos)
+            }
+            LevelProblem::OutOfBounds(pos, key) => {
+                write!(f, "{:?} at {:?} is out of bounds", key, pos)
+            }
+            LevelProblem::Overlap(cell, first, second) => write!(
+                f,
+                "tiles at {:?} and {:?} both occupy {:?}",
+                first, second, cell
+            ),
+            LevelProblem::NotUnique(key, count) => {
+                write!(f, "{:?} must be unique, but appears {} times", key, count)
+            }
+            LevelProblem::Missing(key) => write!(f, "{:?} is mandatory, but missing", key),
+            LevelProblem::StrayWire(pos) => write!(f, "wiring at {:?}, but no tile", pos),
+            LevelProblem::StrayPath(pos) => {
+                write!(f, "path at {:?}, but no moving platform", pos)
+            }
+        }
+    }
+}
+
+/// Checks the level against the tile definitions. Returns all problems that were found, in a
+/// deterministic order. An empty list means the level is fine.
+pub fn validate(level: &LevelSave, tile_defs: &TileDefinitions) -> Vec<LevelProblem> {
+    let mut problems = Vec::new();
+    let tiles: BTreeMap<_, _> = level.tiles.iter().collect();
+    let mut occupied = HashMap::new();
+    let mut counts = HashMap::new();
+    for (pos, key) in &tiles {
+        let tile_def = match tile_defs.map.get(*key) {
+            Some(tile_def) => tile_def,
+            None => {
+                problems.push(LevelProblem::UnknownTile(**pos, key.to_string()));
+                continue;
+            }
+        };
+        *counts.entry(key.as_str()).or_insert(0) += 1;
+        if !level.world_bounds.encloses(pos, &tile_def.dimens) {
+            problems.push(LevelProblem::OutOfBounds(**pos, key.to_string()));
+        }
+        for x in 0..tile_def.dimens.x {
+            for y in 0..tile_def.dimens.y {
+                let cell = pos.append_xy(x, y);
+                if let Some(other) = occupied.insert(cell, **pos) {
+                    problems.push(LevelProblem::Overlap(cell, other, **pos));
+                }
+            }
+        }
+    }
+    let definitions: BTreeMap<_, _> = tile_defs.map.iter().collect();
+    for (key, tile_def) in definitions {
+        let count = counts.get(key.as_str()).copied().unwrap_or(0);
+        if tile_def.unique && count > 1 {
+            problems.push(LevelProblem::NotUnique(key.clone(), count));
+        }
+        if tile_def.mandatory && count == 0 {
+            problems.push(LevelProblem::Missing(key.clone()));
+        }
+    }
+    let wiring: BTreeMap<_, _> = level.wiring.iter().collect();
+    for pos in wiring.keys() {
+        if !level.tiles.contains_key(pos) {
+            problems.push(LevelProblem::StrayWire(**pos));
+        }
+    }
+    let paths: BTreeMap<_, _> = level.paths.iter().collect();
+    for pos in paths.keys() {
+        let is_platform = level
+            .tiles
+            .get(pos)
+            .and_then(|key| tile_defs.map.get(key))
+            .is_some_and(|tile_def| tile_def.platform().is_some());
+        if !is_platform {
+            problems.push(LevelProblem::StrayPath(**pos));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::levels::world_bounds::WorldBounds;
+
+    use super::*;
+
+    fn tile_defs() -> TileDefinitions {
+        ron::de::from_str(include_str!("../../../assets/world/tile_definitions.ron")).unwrap()
+    }
+
+    fn level(tiles: &[(i32, i32, &str)]) -> LevelSave {
+        let mut level = LevelSave {
+            world_bounds: WorldBounds::new(0, 0, 12, 8),
+            ..LevelSave::default()
+        };
+        for (x, y, key) in tiles {
+            level.tiles.insert(Pos::new(*x, *y), key.to_string());
+        }
+        level
+    }
+
+    #[test]
+    fn accepts_valid_levels() {
+        let level = level(&[(0, 0, "Block1"), (0, 1, "Player"), (4, 0, "Door")]);
+        assert_eq!(validate(&level, &tile_defs()), vec![]);
+    }
+
+    #[test]
+    fn reports_broken_tiles() {
+        let mut level = level(&[
+            (0, 0, "Player"),
+            (1, 1, "Player"),
+            (4, 0, "Door"),
+            (10, 5, "Door"),
+            (3, 3, "NoSuchTile"),
+        ]);
+        level.wiring.insert(Pos::new(6, 0), "A".to_string());
+        level.paths.insert(Pos::new(0, 0), vec![Pos::new(0, 2)]);
+        assert_eq!(
+            validate(&level, &tile_defs()),
+            vec![
+                LevelProblem::Overlap(Pos::new(1, 1), Pos::new(0, 0), Pos::new(1, 1)),
+                LevelProblem::UnknownTile(Pos::new(3, 3), "NoSuchTile".to_string()),
+                LevelProblem::OutOfBounds(Pos::new(10, 5), "Door".to_string()),
+                LevelProblem::NotUnique("Door".to_string(), 2),
+                LevelProblem::NotUnique("Player".to_string(), 2),
+                LevelProblem::StrayWire(Pos::new(6, 0)),
+                LevelProblem::StrayPath(Pos::new(0, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_missing_mandatory_tiles() {
+        let level = level(&[(0, 1, "Player")]);
+        assert_eq!(
+            validate(&level, &tile_defs()),
+            vec![LevelProblem::Missing("Door".to_string())]
+        );
+    }
+}
@@ -134,6 +134,8 @@ pub enum SoundType {
     LevelSelect,
     SpawnerOpenClose,
     TrapWallCreated,
+    /// Played when a tool is used for the last time and breaks.
+    ToolBreak,
 }
 
 /// Identifies a music track.
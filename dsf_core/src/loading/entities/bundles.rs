@@ -2,6 +2,8 @@ use bevy::prelude::*;
 
 use crate::systems::animations::structs::AnimationTimer;
 use crate::systems::enemy::spawner::Enemy;
+use crate::systems::inventory::Inventory;
+use crate::systems::locks::KeyRing;
 use crate::systems::motion::move_enemy::EnemyAi;
 use crate::systems::motion::structs::player::Player;
 use crate::systems::motion::structs::steering::Steering;
@@ -15,6 +17,8 @@ pub struct PlayerBundle {
     pub steering: Steering,
     pub player: Player,
     pub anim: AnimationTimer,
+    pub key_ring: KeyRing,
+    pub inventory: Inventory,
     // pub focal_point: FocalPoint,
 }
 
@@ -1,26 +1,37 @@
 use bevy::prelude::*;
 
-use crate::levels::tiles::objects::{ExitDoor, Key, Tool};
+use crate::levels::tiles::objects::{Collectible, ExitDoor, Key, LevelTile, Tool};
 use crate::levels::tiles::tile_defs::{Archetype, DepthLayer, TileDefinition};
 use crate::loading::assets::{AssetStorage, AssetType, SpriteType};
 use crate::loading::entities::bundles::{EnemyBundle, PlayerBundle};
 use crate::systems::animations::structs::AnimationTimer;
+use crate::systems::checkpoint::Checkpoint;
+use crate::systems::crumbling_floor::CrumblingFloor;
+use crate::systems::death::hazard::Hazard;
+use crate::systems::death::lives::ExtraLife;
 use crate::systems::enemy::spawner::Spawner;
+use crate::systems::falling_block::FallingBlock;
+use crate::systems::locks::{ColoredKey, LockedDoor};
 use crate::systems::motion::structs::coords::Coords;
-use crate::systems::motion::structs::direction::Direction1D;
 use crate::systems::motion::structs::pos::Pos;
 use crate::systems::motion::structs::steering::Steering;
+use crate::systems::platform::Platform;
 use crate::systems::revolving_door::RevolvingDoor;
+use crate::systems::signals::{Mechanism, Trigger};
 use crate::systems::trap_wall::TrappedWall;
 
+/// Spawns the entity for the tile definition with the given key and returns it, so the caller can
+/// attach any level-specific components (such as signal wiring).
 pub fn spawn_from_def(
     commands: &mut Commands,
     storage: &AssetStorage,
     pos: Pos,
+    key: &str,
     tile_def: &TileDefinition,
-) {
+) -> Entity {
     let mut entity = commands.spawn();
     entity.insert(Coords::new(pos, tile_def.dimens));
+    entity.insert(LevelTile(key.to_string()));
     if tile_def.asset.is_some() {
         entity.insert_bundle(inflate_sprite_sheet(
             pos,
@@ -29,7 +40,7 @@ pub fn spawn_from_def(
             storage,
         ));
     }
-    match tile_def.archetype {
+    match &tile_def.archetype {
         Some(Archetype::Player) => {
             entity.insert_bundle(PlayerBundle {
                 steering: Steering::new(pos),
@@ -40,27 +51,62 @@ pub fn spawn_from_def(
         Some(Archetype::Key) => {
             entity.insert(Key);
         }
-        Some(Archetype::Tool(tool_type)) => {
+        Some(Archetype::Tool(definition)) => {
+            let definition = definition.clone();
             if let Some(AssetType(sprite, sprite_nr)) = tile_def.asset {
-                entity.insert(Tool::new(tool_type, sprite, sprite_nr));
+                entity.insert(Tool::new(definition, sprite, sprite_nr));
             } else {
-                error!("Tool definition {:?} did not have still asset.", tool_type);
+                error!("Tool definition {:?} did not have still asset.", definition);
             }
         }
         Some(Archetype::Door) => {
             entity.insert(ExitDoor);
         }
-        Some(Archetype::Spawner) => {
-            entity.insert(Spawner::default());
+        Some(Archetype::Spawner(definition)) => {
+            entity.insert(Spawner::new(definition));
         }
-        Some(Archetype::TrapWall) => {
-            entity.insert(TrappedWall::default());
+        Some(Archetype::TrapWall(trap)) => {
+            entity.insert(TrappedWall::new(trap.clone()));
         }
-        Some(Archetype::RevolvingDoor(direction)) => {
-            entity.insert(RevolvingDoor::new(direction));
+        Some(Archetype::RevolvingDoor(door)) => {
+            entity.insert(RevolvingDoor::new(door.clone()));
+        }
+        Some(Archetype::Trigger(trigger_type)) => {
+            entity.insert(Trigger::new(trigger_type.clone()));
+        }
+        Some(Archetype::Mechanism(mechanism)) => {
+            entity.insert(Mechanism::new(mechanism.clone()));
+        }
+        Some(Archetype::Platform(platform)) => {
+            entity.insert(Platform::new(platform.clone(), pos));
+        }
+        Some(Archetype::CrumblingFloor(floor)) => {
+            entity.insert(CrumblingFloor::new(floor.clone()));
+        }
+        Some(Archetype::FallingBlock) => {
+            entity.insert(FallingBlock::default());
+        }
+        Some(Archetype::ColoredKey(color)) => {
+            entity.insert(ColoredKey(*color));
+        }
+        Some(Archetype::LockedDoor(color)) => {
+            entity.insert(LockedDoor(*color));
+        }
+        Some(Archetype::Collectible(score)) => {
+            entity.insert(Collectible { score: *score });
+        }
+        Some(Archetype::ExtraLife) => {
+            entity.insert(ExtraLife);
+        }
+        Some(Archetype::Checkpoint) => {
+            entity.insert(Checkpoint);
         }
         _ => (),
     };
+    if let Some(hazard) = tile_def.hazard {
+        entity.insert(Hazard(hazard));
+    }
+    entity.id()
 }
 
 pub fn spawn_enemy(commands: &mut Commands, storage: &AssetStorage, coords: Coords) -> Entity {
@@ -96,11 +142,14 @@ pub fn inflate_sprite_sheet(
 ) -> SpriteSheetBundle {
     let AssetType(sprite_type, index) = asset;
     let texture_atlas = storage.get_atlas(&sprite_type);
-    let flip_x = tile_def.archetype == Some(Archetype::RevolvingDoor(Direction1D::Negative));
+    let flip_x = tile_def
+        .revolving()
+        .is_some_and(|door| door.facing.is_negative());
     SpriteSheetBundle {
         sprite: TextureAtlasSprite {
             index,
             flip_x,
+            color: tile_def.color(),
             custom_size: Some(tile_def.dimens.as_vec2()),
             ..default()
         },
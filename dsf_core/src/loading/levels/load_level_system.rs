@@ -4,6 +4,7 @@ use bevy::prelude::*;
 
 use crate::camera::camera_components::FocalPoint;
 use crate::level_select::structs::LevelSelectionInstruction;
+use crate::levels::level_save::LevelSave;
 use crate::levels::tiles::background::{BackgroundEyes, BackgroundHeads, BackgroundTag};
 use crate::levels::tiles::tile_defs::{DepthLayer, TileDefinitions};
 use crate::levels::tiles::tilemap::TileMap;
@@ -11,7 +12,12 @@ use crate::levels::world_bounds::WorldBounds;
 use crate::loading::assets::{AssetStorage, SpriteType};
 use crate::loading::entities::inflate::spawn_from_def;
 use crate::states::LevelLoaded;
+use crate::systems::checkpoint::CheckpointSave;
+use crate::systems::death::cause::DeathLog;
+use crate::systems::platform::Waypoints;
 use crate::systems::rewind::structs::History;
+use crate::systems::scoring::LevelStats;
+use crate::systems::signals::{SignalWire, Signals};
 use crate::systems::win_checking::WinCondition;
 use crate::util::files::{get_world_dir, load_level_file};
 
@@ -19,29 +25,65 @@ pub fn load_level(
     mut commands: Commands,
     mut events: EventWriter<LevelLoaded>,
     mut win_condition: ResMut<WinCondition>,
+    mut death_log: ResMut<DeathLog>,
+    mut checkpoint: ResMut<CheckpointSave>,
     storage: Res<AssetStorage>,
     instruction: Res<LevelSelectionInstruction>,
 ) {
     win_condition.reset();
+    death_log.start_level(&instruction.level);
+    commands.insert_resource(LevelStats::new(death_log.causes.len()));
     let tile_defs = load_tile_definitions();
-    let level = load_level_file(instruction.level.as_ref().unwrap());
+    checkpoint.start_level(&instruction.level);
+    let level = checkpoint
+        .level()
+        .cloned()
+        .unwrap_or_else(|| load_level_file(instruction.level.as_ref().unwrap()));
     add_background(&level.world_bounds, &mut commands, &storage);
+    spawn_level(&mut commands, &storage, &level, tile_defs);
+    events.send(LevelLoaded);
+}
+
+/// Spawns all tiles in the level and sets up the resources that keep track of the level while it
+/// is being played: the `TileMap`, the `Signals` and the `History`.
+pub fn spawn_level(
+    commands: &mut Commands,
+    storage: &AssetStorage,
+    level: &LevelSave,
+    tile_defs: TileDefinitions,
+) {
     level.tiles.iter().for_each(|(pos, tile_def_key)| {
         debug!("Load {:?} at {:?}.", tile_def_key, pos);
         let tile_def = tile_defs.get(tile_def_key);
-        spawn_from_def(&mut commands, &storage, *pos, tile_def);
+        let entity = spawn_from_def(commands, storage, *pos, tile_def_key, tile_def);
+        if let Some(signal) = level.wiring.get(pos) {
+            commands
+                .entity(entity)
+                .insert(SignalWire::new(signal.clone()));
+        }
+        if let Some(path) = level.paths.get(pos) {
+            commands.entity(entity).insert(Waypoints(path.clone()));
+        }
     });
-    commands.insert_resource(TileMap::for_play(&level, tile_defs));
+    commands.insert_resource(TileMap::for_play(level, tile_defs));
+    commands.insert_resource(Signals::default());
     commands.insert_resource(History::default());
-    events.send(LevelLoaded);
 }
 
 /// Loads the TileDefinitions from file.
 #[must_use]
 pub fn load_tile_definitions() -> TileDefinitions {
+    try_load_tile_definitions().unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Like `load_tile_definitions`, but returns an error instead of panicking. For tools that run
+/// outside of the game.
+pub fn try_load_tile_definitions() -> Result<TileDefinitions, String> {
     let file = get_world_dir().join("tile_definitions.ron");
-    let data = fs::read_to_string(file).expect("Unable to read TileDefinitions file");
-    ron::de::from_str::<TileDefinitions>(&data).expect("Unable to deserialise TileDefinitions")
+    let data = fs::read_to_string(&file)
+        .map_err(|error| format!("Unable to read TileDefinitions file {:?}: {}", file, error))?;
+    ron::de::from_str::<TileDefinitions>(&data)
+        .map_err(|error| format!("Unable to deserialise TileDefinitions: {}", error))
 }
 
 pub fn add_background(world_bounds: &WorldBounds, commands: &mut Commands, storage: &AssetStorage) {
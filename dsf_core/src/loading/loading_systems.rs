@@ -6,16 +6,19 @@ use bevy::prelude::*;
 use iyes_loopless::prelude::NextState;
 
 use crate::config::editor_config::EditorConfig;
+use crate::config::launch_options::LaunchOptions;
 use crate::config::loading_config::LoadingConfig;
 use crate::config::movement_config::MovementConfig;
 use crate::config::settings::audio_settings::AudioSettings;
 use crate::config::settings::debug_settings::DebugSettings;
+use crate::config::settings::difficulty_settings::DifficultySettings;
 use crate::config::settings::progression::Progression;
 use crate::level_select::structs::LevelSelectionInstruction;
 use crate::loading::assets::AssetStorage;
 use crate::loading::atlas_prefab::AtlasPrefab;
 use crate::states::AppState;
-use crate::util::files::{get_assets_dir, get_atlases_dir, get_levels_dir};
+use crate::systems::death::lives::Lives;
+use crate::util::files::{get_adventures_dir, get_assets_dir, get_atlases_dir, get_levels_dir};
 
 #[derive(Default, Debug)]
 pub struct LoadingAssets {
@@ -84,10 +87,24 @@ pub fn load_assets(
     commands.insert_resource(loading_assets);
 }
 
-pub fn load_configs(mut commands: Commands) {
+pub fn load_configs(mut commands: Commands, options: Res<LaunchOptions>) {
     commands.insert_resource(AudioSettings::load_from_file());
-    commands.insert_resource(DebugSettings::load_from_file());
+    let debug_settings = DebugSettings::load_from_file();
+    let debug_settings = match options.override_debug_settings(&debug_settings) {
+        Ok(overridden) => overridden,
+        Err(error) => {
+            error!(
+                "Ignoring the debug settings given on the command line: {}",
+                error
+            );
+            debug_settings
+        }
+    };
+    commands.insert_resource(debug_settings);
     commands.insert_resource(Progression::load_from_file());
+    let difficulty = DifficultySettings::load_from_file();
+    commands.insert_resource(Lives::new(&difficulty));
+    commands.insert_resource(difficulty);
 
     commands.insert_resource(MovementConfig::load_from_file());
     commands.insert_resource(EditorConfig::load_from_file());
@@ -101,6 +118,7 @@ pub fn check_load_state(
     asset_server: Res<AssetServer>,
     loading_assets: Res<LoadingAssets>,
     config: Res<DebugSettings>,
+    options: Res<LaunchOptions>,
 ) {
     match asset_server.get_group_load_state(loading_assets.handles.iter().map(|h| h.id)) {
         LoadState::Failed => {
@@ -108,7 +126,17 @@ pub fn check_load_state(
             // one of our assets had an error
         }
         LoadState::Loaded => {
-            if config.use_alternate_menu {
+            if let Some(level) = &options.play {
+                info!("Done loading, switching to level {:?}!", level);
+                // The level select screen is where the player ends up after quitting the level.
+                instruction.adventure = Some(get_adventures_dir().join("default.ron"));
+                instruction.level = Some(level.clone());
+                commands.insert_resource(NextState(AppState::InGame));
+            } else if let Some(level) = &options.edit {
+                info!("Done loading, switching to level editor for {:?}!", level);
+                instruction.level = Some(level.clone());
+                commands.insert_resource(NextState(AppState::LevelEditor));
+            } else if config.use_alternate_menu {
                 info!("Done loading, switching to alt menu!");
                 commands.insert_resource(NextState(AppState::AltMenu));
             } else if config.skip_straight_to_editor {
@@ -6,6 +6,7 @@ use crate::audio::sound_event::SoundEvent;
 use crate::config::settings::debug_settings::DebugSettings;
 use crate::level_select::structs::LevelSelectionInstruction;
 use crate::loading::assets::MusicType;
+use crate::systems::checkpoint::CheckpointSave;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum AppState {
@@ -16,6 +17,7 @@ pub enum AppState {
     InGame,
     LevelEditor,
     Settings,
+    GameOver,
 }
 
 pub fn delete_all_entities(mut commands: Commands, query: Query<Entity>) {
@@ -31,9 +33,11 @@ pub fn back_on_escape(
     mut keys: ResMut<Input<KeyCode>>,
     config: Res<DebugSettings>,
     current_state: Res<CurrentState<AppState>>,
+    mut checkpoint: ResMut<CheckpointSave>,
 ) {
     if keys.clear_just_pressed(KeyCode::Escape) {
         let next = if current_state.0 == AppState::InGame {
+            checkpoint.clear();
             if instruction.editor_open {
                 AppState::LevelEditor
             } else if config.use_alternate_menu {
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionSet};
+
+use crate::states::{delete_all_entities, AppState};
+use crate::systems::death::lives::refill_lives;
+use crate::systems::menu::alt_menu::animate_buttons;
+use crate::systems::menu::game_over::{read_game_over_input, setup_game_over};
+
+pub struct GameOverState;
+
+impl Plugin for GameOverState {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system_set(
+            AppState::GameOver,
+            ConditionSet::new()
+                .run_in_state(AppState::GameOver)
+                .with_system(setup_game_over)
+                .into(),
+        )
+        .add_system_set(
+            ConditionSet::new()
+                .run_in_state(AppState::GameOver)
+                .with_system(read_game_over_input)
+                .with_system(animate_buttons)
+                .into(),
+        )
+        .add_exit_system_set(
+            AppState::GameOver,
+            ConditionSet::new()
+                .run_in_state(AppState::GameOver)
+                .with_system(delete_all_entities)
+                .with_system(refill_lives)
+                .into(),
+        );
+    }
+}
@@ -2,7 +2,7 @@ use bevy::app::CoreStage::Update;
 use bevy::prelude::*;
 use iyes_loopless::prelude::*;
 
-use crate::camera::camera_systems::camera_follow_focal_point;
+use crate::camera::camera_systems::{camera_follow_focal_point, fit_camera_to_level};
 use crate::camera::create_camera::create_camera;
 use crate::loading::levels::debug_frames::build_frames;
 use crate::loading::levels::keys_on_door::add_key_displays_to_door;
@@ -11,20 +11,34 @@ use crate::states::{back_on_escape, delete_all_entities, start_music, AppState};
 use crate::systems::animations::walk_anim::animate_walking;
 use crate::systems::background_anim::{anim_background_eyes, anim_background_heads};
 use crate::systems::check_input::check_in_game_input;
+use crate::systems::checkpoint::{
+    restore_checkpoint, save_checkpoint, touch_checkpoints, CheckpointReached,
+};
+use crate::systems::crumbling_floor::{crumble_floors, trigger_crumbling_floors};
 use crate::systems::death::death_anim::{animate_death, is_dying};
+use crate::systems::death::hazard::touch_hazards;
+use crate::systems::death::lives::collect_extra_lives;
 use crate::systems::debug::debug_system;
 use crate::systems::enemy::kill::enemy_kill;
-use crate::systems::enemy::spawner::activate_spawners;
-use crate::systems::menu::setup_hud::setup_hud;
+use crate::systems::enemy::spawner::{activate_spawners, toggle_wired_spawners};
+use crate::systems::falling_block::drop_falling_blocks;
+use crate::systems::inventory::{cycle_tools, drop_tool, show_equipped_tool};
+use crate::systems::locks::{pickup_colored_keys, unlock_doors};
+use crate::systems::menu::setup_hud::{setup_hud, show_held_keys, show_inventory, show_lives};
 use crate::systems::motion::move_enemy::set_enemy_steering_intent;
 use crate::systems::motion::move_player::set_player_steering_intent;
 use crate::systems::motion::movement::{movement_system, velocity_system};
 use crate::systems::motion::steering::steering_system;
+use crate::systems::platform::move_platforms;
 use crate::systems::revolving_door::{
     control_revolving_doors, control_revolving_sprites, set_revolving_controllers,
 };
-use crate::systems::tools::{pickup_system, use_tool_system};
-use crate::systems::trap_wall::{trap_mechanism, trigger_trap_walls};
+use crate::systems::scoring::{collect_treasure, tick_level_time};
+use crate::systems::signals::{switch_mechanisms, update_triggers, SignalChanged};
+use crate::systems::tools::{
+    animate_broken_tools, expire_temporary_tiles, pickup_system, use_tool_system,
+};
+use crate::systems::trap_wall::{fire_wired_traps, trap_mechanism, trigger_trap_walls};
 use crate::systems::win_checking::{check_if_won, key_collect_system};
 use crate::systems::win_handling::{
     clean_resources, handle_win_door, handle_win_player, handle_win_queued, has_won,
@@ -36,7 +50,7 @@ pub struct InGameState;
 
 impl Plugin for InGameState {
     fn build(&self, app: &mut App) {
-        app.add_event::<LevelLoaded>()
+        app.add_plugin(GameplayPlugin)
             .add_enter_system_set(
                 AppState::InGame,
                 ConditionSet::new()
@@ -46,6 +60,66 @@ impl Plugin for InGameState {
                     .with_system(start_music)
                     .into(),
             )
+            .add_system_set_to_stage(
+                "finish_setup",
+                ConditionSet::new()
+                    .run_in_state(AppState::InGame)
+                    .run_on_event::<LevelLoaded>()
+                    .with_system(setup_hud)
+                    .with_system(build_frames)
+                    .with_system(fit_camera_to_level)
+                    .into(),
+            )
+            .add_system_set_to_stage(
+                "other",
+                ConditionSet::new()
+                    .run_in_state(AppState::InGame)
+                    .with_system(back_on_escape.run_if_not(has_won))
+                    .with_system(camera_follow_focal_point)
+                    // .with_system(camera_control)
+                    .with_system(show_equipped_tool)
+                    .with_system(show_held_keys)
+                    .with_system(show_lives)
+                    .with_system(show_inventory)
+                    .with_system(debug_system)
+                    // .with_system(rewind_control_system)
+                    // .with_system(rewind_system)
+                    .with_system(anim_background_heads)
+                    .with_system(anim_background_eyes)
+                    .with_system(animate_walking.run_if_not(has_won))
+                    .with_system(animate_death.run_if_not(has_won))
+                    .with_system(check_in_game_input.run_if_not(is_dying))
+                    .with_system(control_revolving_sprites.run_if_not(has_won))
+                    .with_system(animate_broken_tools)
+                    .with_system(handle_win_queued.run_if(has_won))
+                    .with_system(handle_win_door.run_if(has_won))
+                    .with_system(handle_win_player.run_if(has_won))
+                    .into(),
+            )
+            .add_exit_system_set(
+                AppState::InGame,
+                ConditionSet::new()
+                    .run_in_state(AppState::InGame)
+                    .with_system(delete_all_entities)
+                    .with_system(clean_resources)
+                    .into(),
+            );
+    }
+}
+
+/// The stages and systems that make up the game's rules: movement, tools, keys, traps, doors and
+/// everything else that decides what happens in a level. It contains nothing that draws, plays
+/// audio or changes state, so it can also run headless, without a window.
+///
+/// Levels are not loaded by this plugin. Spawn them with `spawn_level` and send a `LevelLoaded`
+/// event to finish setting them up.
+pub struct GameplayPlugin;
+
+impl Plugin for GameplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LevelLoaded>()
+            .add_event::<SignalChanged>()
+            .add_event::<CheckpointReached>()
             .add_stage_before(Update, "finish_setup", SystemStage::parallel())
             .add_system_set_to_stage(
                 "finish_setup",
@@ -53,9 +127,8 @@ impl Plugin for InGameState {
                     .run_in_state(AppState::InGame)
                     .run_on_event::<LevelLoaded>()
                     .with_system(set_revolving_controllers)
-                    .with_system(setup_hud)
                     .with_system(add_key_displays_to_door)
-                    .with_system(build_frames)
+                    .with_system(restore_checkpoint)
                     .into(),
             )
             .add_stage_before(Update, "set_intent", SystemStage::parallel())
@@ -91,39 +164,35 @@ impl Plugin for InGameState {
                 "other",
                 ConditionSet::new()
                     .run_in_state(AppState::InGame)
-                    .with_system(back_on_escape.run_if_not(has_won))
-                    .with_system(camera_follow_focal_point)
-                    // .with_system(camera_control)
                     .with_system(velocity_system.run_if_not(has_won))
                     .with_system(pickup_system)
                     .with_system(use_tool_system)
+                    .with_system(drop_tool.run_if_not(has_won).run_if_not(is_dying))
+                    .with_system(cycle_tools.run_if_not(has_won).run_if_not(is_dying))
                     .with_system(key_collect_system)
-                    .with_system(debug_system)
-                    // .with_system(rewind_control_system)
-                    // .with_system(rewind_system)
-                    .with_system(anim_background_heads)
-                    .with_system(anim_background_eyes)
-                    .with_system(animate_walking.run_if_not(has_won))
-                    .with_system(animate_death.run_if_not(has_won))
-                    .with_system(check_in_game_input.run_if_not(is_dying))
+                    .with_system(pickup_colored_keys.run_if_not(has_won))
+                    .with_system(unlock_doors.run_if_not(has_won))
+                    .with_system(collect_extra_lives.run_if_not(has_won))
+                    .with_system(touch_checkpoints.run_if_not(has_won).run_if_not(is_dying))
+                    .with_system(save_checkpoint.run_on_event::<CheckpointReached>())
+                    .with_system(collect_treasure.run_if_not(has_won))
+                    .with_system(tick_level_time.run_if_not(has_won))
                     .with_system(activate_spawners.run_if_not(has_won))
                     .with_system(enemy_kill.run_if_not(is_dying))
+                    .with_system(touch_hazards.run_if_not(has_won))
                     .with_system(trigger_trap_walls.run_if_not(has_won))
                     .with_system(trap_mechanism.run_if_not(has_won))
-                    .with_system(control_revolving_sprites.run_if_not(has_won))
                     .with_system(control_revolving_doors.run_if_not(has_won))
+                    .with_system(update_triggers.run_if_not(has_won))
+                    .with_system(switch_mechanisms.run_if_not(has_won))
+                    .with_system(toggle_wired_spawners.run_if_not(has_won))
+                    .with_system(fire_wired_traps.run_if_not(has_won))
+                    .with_system(move_platforms.run_if_not(has_won))
+                    .with_system(trigger_crumbling_floors.run_if_not(has_won))
+                    .with_system(crumble_floors.run_if_not(has_won))
+                    .with_system(expire_temporary_tiles.run_if_not(has_won))
+                    .with_system(drop_falling_blocks.run_if_not(has_won))
                     .with_system(check_if_won.run_if_not(has_won))
-                    .with_system(handle_win_queued.run_if(has_won))
-                    .with_system(handle_win_door.run_if(has_won))
-                    .with_system(handle_win_player.run_if(has_won))
-                    .into(),
-            )
-            .add_exit_system_set(
-                AppState::InGame,
-                ConditionSet::new()
-                    .run_in_state(AppState::InGame)
-                    .with_system(delete_all_entities)
-                    .with_system(clean_resources)
                     .into(),
             );
     }
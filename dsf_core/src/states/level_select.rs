@@ -5,7 +5,7 @@ use crate::camera::camera_systems::{camera_control, camera_follow_focal_point};
 use crate::camera::create_camera::create_camera;
 use crate::level_select::create_default_adventure::create_default_adventure;
 use crate::level_select::setup_systems::on_start;
-use crate::level_select::update_systems::{check_input, update_cursor, update_ui};
+use crate::level_select::update_systems::{check_input, show_thumbnail, update_cursor, update_ui};
 use crate::states::{back_on_escape, delete_all_entities, start_music, AppState};
 
 pub struct LevelSelectState;
@@ -38,6 +38,7 @@ impl Plugin for LevelSelectState {
                 .with_system(camera_follow_focal_point)
                 .with_system(camera_control)
                 .with_system(update_cursor)
+                .with_system(show_thumbnail)
                 .with_system(update_ui)
                 .into(),
         )
@@ -1,5 +1,6 @@
 pub use self::alt_menu::*;
 pub use self::app_state::*;
+pub use self::game_over::*;
 pub use self::in_game::*;
 pub use self::level_select::*;
 pub use self::loading::*;
@@ -8,6 +9,7 @@ pub use self::settings::*;
 
 mod alt_menu;
 mod app_state;
+mod game_over;
 mod in_game;
 mod level_select;
 mod loading;
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use iyes_loopless::prelude::NextState;
 
 use crate::states::AppState;
+use crate::systems::checkpoint::CheckpointSave;
+use crate::systems::death::cause::DeathCause;
 use crate::systems::death::death_anim::Dying;
 use crate::systems::enemy::spawner::Enemy;
 use crate::systems::motion::structs::player::Player;
@@ -11,11 +13,16 @@ pub fn check_in_game_input(
     query: Query<Entity, With<Player>>,
     query_enemy: Query<Entity, With<Enemy>>,
     mut keys: ResMut<Input<KeyCode>>,
+    mut checkpoint: ResMut<CheckpointSave>,
 ) {
     // Reset the level
     if keys.clear_just_pressed(KeyCode::F5) {
+        // Resetting starts the level from the very beginning, not from the last checkpoint.
+        checkpoint.clear();
         if let Ok(entity) = query.get_single() {
-            commands.entity(entity).insert(Dying::default());
+            commands
+                .entity(entity)
+                .insert(Dying::new(DeathCause::Reset));
         }
     }
     if keys.clear_just_pressed(KeyCode::F4) {
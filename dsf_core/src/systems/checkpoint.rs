@@ -0,0 +1,229 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::audio::sound_event::SoundEvent;
+use crate::levels::level_save::LevelSave;
+use crate::levels::tiles::objects::LevelTile;
+use crate::levels::tiles::tilemap::TileMap;
+use crate::loading::assets::SoundType;
+use crate::systems::inventory::Inventory;
+use crate::systems::locks::KeyRing;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::player::Player;
+use crate::systems::motion::structs::pos::Pos;
+use crate::systems::platform::{Platform, Waypoints};
+use crate::systems::revolving_door::RevolvingDoor;
+use crate::systems::scoring::LevelStats;
+use crate::systems::signals::{SignalWire, Trigger};
+use crate::systems::tools::Temporary;
+use crate::systems::trap_wall::TrappedWall;
+use crate::systems::win_checking::WinCondition;
+
+/// The color of the checkpoint that the player will return to when they die.
+const ACTIVE_COLOR: Color = Color::rgb(1., 0.85, 0.3);
+
+type TileComponents<'a> = (
+    &'a LevelTile,
+    &'a Coords,
+    Option<&'a SignalWire>,
+    Option<&'a Waypoints>,
+    Option<&'a Platform>,
+    Option<&'a RevolvingDoor>,
+    Option<&'a Trigger>,
+    Option<&'a TrappedWall>,
+);
+
+type RestoreComponents<'a> = (
+    &'a Coords,
+    Option<&'a mut Trigger>,
+    Option<&'a mut TrappedWall>,
+    Option<&'a Checkpoint>,
+    Option<&'a mut TextureAtlasSprite>,
+);
+
+/// A tile that saves the player's progress through the level when they touch it.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Checkpoint;
+
+/// Sent when the player touches a checkpoint that is not already the active checkpoint.
+pub struct CheckpointReached;
+
+/// Everything about the level that can change while it is being played, as it was when the player
+/// touched a checkpoint.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// The level in its current state, with all tiles at their current positions. Collected keys,
+    /// broken blocks and tools that were picked up are no longer in it, grown trap walls are.
+    level: LevelSave,
+    keys_collected: usize,
+    key_ring: KeyRing,
+    inventory: Inventory,
+    /// Positions of all triggers that were emitting their signal.
+    active_triggers: Vec<Pos>,
+    /// Positions of all trap wall segments that were counting down.
+    triggered_traps: Vec<Pos>,
+    score: u32,
+    time: f32,
+}
+
+/// Keeps the snapshot of the last checkpoint the player touched.
+/// Like the `DeathLog`, it is kept for as long as the player keeps retrying the same level.
+#[derive(Debug, Default)]
+pub struct CheckpointSave {
+    /// The level that the checkpoint is in.
+    level: Option<PathBuf>,
+    /// The position of the checkpoint tile that the player touched last.
+    active: Option<Pos>,
+    snapshot: Option<Snapshot>,
+}
+
+impl CheckpointSave {
+    /// Forgets the checkpoint if the given level is not the level it is in.
+    pub fn start_level(&mut self, level: &Option<PathBuf>) {
+        if &self.level != level {
+            self.clear();
+            self.level = level.clone();
+        }
+    }
+
+    /// Forgets the checkpoint, so the level will be started from the beginning.
+    pub fn clear(&mut self) {
+        self.level = None;
+        self.active = None;
+        self.snapshot = None;
+    }
+
+    /// Returns the state of the level as it was when the player touched the last checkpoint.
+    #[must_use]
+    pub fn level(&self) -> Option<&LevelSave> {
+        self.snapshot.as_ref().map(|snapshot| &snapshot.level)
+    }
+}
+
+/// When the player touches a checkpoint, that checkpoint becomes active. The actual snapshot is
+/// taken by `save_checkpoint`.
+pub fn touch_checkpoints(
+    mut events: EventWriter<CheckpointReached>,
+    mut audio: EventWriter<SoundEvent>,
+    mut save: ResMut<CheckpointSave>,
+    query_player: Query<&Coords, With<Player>>,
+    mut query_checkpoints: Query<(&Coords, &mut TextureAtlasSprite), With<Checkpoint>>,
+) {
+    let touched = query_player.iter().find_map(|player| {
+        query_checkpoints
+            .iter()
+            .map(|(coords, _)| coords)
+            .find(|coords| coords.overlaps(player) && save.active != Some(coords.pos))
+            .map(|coords| coords.pos)
+    });
+    if let Some(pos) = touched {
+        audio.send(SoundEvent::Sfx(SoundType::SpawnerOpenClose, false));
+        save.active = Some(pos);
+        events.send(CheckpointReached);
+        for (coords, mut sprite) in query_checkpoints.iter_mut() {
+            sprite.color = checkpoint_color(coords.pos, &save);
+        }
+    }
+}
+
+/// Takes a snapshot of the level in its current state. Temporary tiles placed by tools are left
+/// out, since they would otherwise never disappear after a restore.
+pub fn save_checkpoint(
+    mut save: ResMut<CheckpointSave>,
+    win: Res<WinCondition>,
+    stats: Res<LevelStats>,
+    tile_map: Res<TileMap>,
+    query_player: Query<(&KeyRing, &Inventory), With<Player>>,
+    query_tiles: Query<TileComponents, Without<Temporary>>,
+) {
+    let (key_ring, inventory) = if let Ok(player) = query_player.get_single() {
+        player
+    } else {
+        return;
+    };
+    let mut level = LevelSave {
+        world_bounds: tile_map.world_bounds,
+        ..default()
+    };
+    let mut active_triggers = Vec::new();
+    let mut triggered_traps = Vec::new();
+    for (level_tile, coords, wire, waypoints, platform, revolving, trigger, trap) in
+        query_tiles.iter()
+    {
+        let pos = coords.pos;
+        let key = revolving
+            .map(|door| door.definition.key_for(door.facing).to_string())
+            .unwrap_or_else(|| level_tile.0.clone());
+        level.tiles.insert(pos, key);
+        if let Some(wire) = wire {
+            level.wiring.insert(pos, wire.signal.clone());
+        }
+        if let Some(waypoints) = waypoints {
+            let route = platform.map_or_else(
+                || waypoints.0.clone(),
+                |platform| platform.remaining_route(waypoints, pos),
+            );
+            level.paths.insert(pos, route);
+        }
+        if trigger.is_some_and(|trigger| trigger.active) {
+            active_triggers.push(pos);
+        }
+        if trap.is_some_and(|trap| trap.timer.is_some()) {
+            triggered_traps.push(pos);
+        }
+    }
+    save.snapshot = Some(Snapshot {
+        level,
+        keys_collected: win.nr_keys_collected(),
+        key_ring: key_ring.clone(),
+        inventory: inventory.clone(),
+        active_triggers,
+        triggered_traps,
+        score: stats.score,
+        time: stats.time,
+    });
+}
+
+/// After the level was loaded from a snapshot, restores everything about the level that is not
+/// stored in the `LevelSave`.
+pub fn restore_checkpoint(
+    save: Res<CheckpointSave>,
+    mut stats: ResMut<LevelStats>,
+    mut win: ResMut<WinCondition>,
+    mut query_player: Query<(&mut KeyRing, &mut Inventory), With<Player>>,
+    mut query_level: Query<RestoreComponents>,
+) {
+    for (coords, trigger, trap, checkpoint, sprite) in query_level.iter_mut() {
+        if let (Some(_), Some(mut sprite)) = (checkpoint, sprite) {
+            sprite.color = checkpoint_color(coords.pos, &save);
+        }
+        if let Some(snapshot) = &save.snapshot {
+            if let Some(mut trigger) = trigger {
+                trigger.active = snapshot.active_triggers.contains(&coords.pos);
+            }
+            if let Some(mut trap) = trap {
+                if snapshot.triggered_traps.contains(&coords.pos) {
+                    trap.start_timer();
+                }
+            }
+        }
+    }
+    if let Some(snapshot) = &save.snapshot {
+        stats.score = snapshot.score;
+        stats.time = snapshot.time;
+        win.restore_keys_collected(snapshot.keys_collected);
+        if let Ok((mut key_ring, mut inventory)) = query_player.get_single_mut() {
+            *key_ring = snapshot.key_ring.clone();
+            *inventory = snapshot.inventory.clone();
+        }
+    }
+}
+
+fn checkpoint_color(pos: Pos, save: &CheckpointSave) -> Color {
+    if save.active == Some(pos) {
+        ACTIVE_COLOR
+    } else {
+        Color::WHITE
+    }
+}
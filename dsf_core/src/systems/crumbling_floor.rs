@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+use crate::audio::sound_event::SoundEvent;
+use crate::levels::tiles::tile_defs::CrumblingFloorDefinition;
+use crate::levels::tiles::tilemap::TileMap;
+use crate::loading::assets::SoundType;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::dimens::Dimens;
+use crate::systems::motion::structs::player::Player;
+use crate::systems::motion::structs::steering::Steering;
+
+/// How far (in tiles) a crumbling floor shakes back and forth while it is about to collapse.
+const SHAKE_AMPLITUDE: f32 = 0.05;
+
+#[derive(Component, Default)]
+pub struct CrumblingFloor {
+    /// Countdown to the floor crumbling. Starts the first time the player stands on the floor.
+    pub timer: Option<Timer>,
+    pub definition: CrumblingFloorDefinition,
+}
+
+impl CrumblingFloor {
+    #[must_use]
+    pub fn new(definition: CrumblingFloorDefinition) -> Self {
+        CrumblingFloor {
+            timer: None,
+            definition,
+        }
+    }
+}
+
+/// Starts the countdown of any crumbling floor the player is standing on. Once the countdown has
+/// started, it cannot be stopped; stepping off the floor will not save it.
+pub fn trigger_crumbling_floors(
+    tile_map: Res<TileMap>,
+    query_player: Query<(&Coords, &Steering), With<Player>>,
+    mut query_floors: Query<(&mut CrumblingFloor, &Coords)>,
+) {
+    for (player, steering) in query_player.iter() {
+        if !steering.is_grounded() {
+            continue;
+        }
+        let beneath_feet = Coords::new(
+            tile_map.world_bounds.wrapped(&player.pos.append_y(-1)),
+            Dimens::new(player.dimens.x, 1),
+        );
+        for (mut floor, _) in query_floors
+            .iter_mut()
+            .filter(|(floor, coords)| floor.timer.is_none() && coords.overlaps(&beneath_feet))
+        {
+            floor.timer = Some(Timer::from_seconds(floor.definition.delay, false));
+        }
+    }
+}
+
+/// Ticks the countdown of crumbling floors. While counting down, the floor shakes. Once the time
+/// runs out, the floor disappears from both the world and the tile map.
+pub fn crumble_floors(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tile_map: ResMut<TileMap>,
+    mut audio: EventWriter<SoundEvent>,
+    mut query: Query<(Entity, &mut CrumblingFloor, &Coords, &mut Transform)>,
+) {
+    for (entity, mut floor, coords, mut transform) in query.iter_mut() {
+        if let Some(timer) = floor.timer.as_mut() {
+            timer.tick(time.delta());
+            if timer.finished() {
+                audio.send(SoundEvent::Sfx(SoundType::Mining, false));
+                tile_map.remove_tile(&coords.pos);
+                commands.entity(entity).despawn_recursive();
+            } else {
+                let (centered_x, _) = coords.to_centered_coords(coords.pos);
+                let shake = (timer.elapsed_secs() * 40.).sin() * SHAKE_AMPLITUDE;
+                transform.translation.x = centered_x + shake;
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::levels::tiles::tile_defs::HazardType;
+use crate::loading::assets::SoundType;
+
+/// Describes what killed the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DeathCause {
+    /// Touched an enemy.
+    Enemy,
+    /// Touched a hazardous tile, such as spikes or fire.
+    Hazard(HazardType),
+    /// Fell from a greater height than the maximum safe fall height.
+    Fall,
+    /// A falling block landed on the player.
+    Crushed,
+    /// The player gave up and reset the level.
+    Reset,
+}
+
+impl DeathCause {
+    /// The sound that plays when the player dies of this cause.
+    #[must_use]
+    pub fn sound(&self) -> SoundType {
+        match self {
+            DeathCause::Reset => SoundType::LvlReset,
+            _ => SoundType::Death,
+        }
+    }
+
+    /// True if the player was squashed flat, rather than simply killed.
+    #[must_use]
+    pub fn is_crushing(&self) -> bool {
+        matches!(
+            self,
+            DeathCause::Crushed | DeathCause::Hazard(HazardType::Crusher)
+        )
+    }
+}
+
+/// Keeps a record of every time the player died while playing a level.
+/// The record is kept for as long as the player keeps retrying the same level.
+#[derive(Debug, Default)]
+pub struct DeathLog {
+    /// The level that these deaths occurred in.
+    level: Option<PathBuf>,
+    /// The causes of all deaths, in the order that they happened.
+    pub causes: Vec<DeathCause>,
+}
+
+impl DeathLog {
+    /// Starts a new record if the given level is not the level that this record is for.
+    pub fn start_level(&mut self, level: &Option<PathBuf>) {
+        if &self.level != level {
+            self.level = level.clone();
+            self.causes.clear();
+        }
+    }
+
+    /// Forgets about all deaths, as if the player never played the level before.
+    pub fn clear(&mut self) {
+        self.level = None;
+        self.causes.clear();
+    }
+
+    pub fn record(&mut self, cause: DeathCause) {
+        self.causes.push(cause);
+    }
+}
@@ -4,36 +4,71 @@ use bevy::prelude::*;
 use iyes_loopless::state::NextState;
 
 use crate::audio::sound_event::SoundEvent;
-use crate::loading::assets::SoundType;
 use crate::states::AppState;
+use crate::systems::death::cause::{DeathCause, DeathLog};
+use crate::systems::death::lives::Lives;
+use crate::systems::motion::structs::coords::Coords;
 use crate::systems::motion::structs::player::Player;
 
-#[derive(Clone, Default, Component)]
+#[derive(Clone, Component)]
 pub struct Dying {
+    pub cause: DeathCause,
     seconds_passed: f32,
     transform: Option<Transform>,
 }
 
+impl Dying {
+    #[must_use]
+    pub fn new(cause: DeathCause) -> Self {
+        Dying {
+            cause,
+            seconds_passed: 0.,
+            transform: None,
+        }
+    }
+}
+
 pub fn is_dying(query: Query<Option<&Dying>, With<Player>>) -> bool {
     matches!(query.get_single(), Ok(Some(_)))
 }
 
+/// Plays the death animation, after which the level is restarted. If the player has no lives
+/// left, the game is over instead. Resetting the level does not cost a life.
 pub fn animate_death(
     mut commands: Commands,
     mut audio: EventWriter<SoundEvent>,
+    mut log: ResMut<DeathLog>,
+    mut lives: ResMut<Lives>,
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut Dying)>,
+    mut query: Query<(&mut Transform, &mut Dying, &Coords), With<Player>>,
 ) {
-    if let Ok((mut transform, mut dying)) = query.get_single_mut() {
+    if let Ok((mut transform, mut dying, coords)) = query.get_single_mut() {
         if dying.transform.is_none() {
-            audio.send(SoundEvent::Sfx(SoundType::Death, true));
+            audio.send(SoundEvent::Sfx(dying.cause.sound(), true));
+            log.record(dying.cause);
+            // Restarting the level on purpose is not punished.
+            if dying.cause != DeathCause::Reset {
+                lives.lose_one();
+            }
             dying.transform = Some(*transform);
         }
         dying.seconds_passed += time.delta_seconds();
         if dying.seconds_passed > 3. {
-            commands.insert_resource(NextState(AppState::InGame));
+            let next = if lives.is_game_over() {
+                AppState::GameOver
+            } else {
+                AppState::InGame
+            };
+            commands.insert_resource(NextState(next));
         } else if dying.seconds_passed > 2.5 {
             // No-op.
+        } else if dying.cause.is_crushing() {
+            // Squash the player flat against the floor.
+            let original = dying.transform.unwrap();
+            let height = (1. - dying.seconds_passed * 4.).max(0.1);
+            transform.scale.y = original.scale.y * height;
+            transform.translation.y =
+                original.translation.y - coords.dimens.y as f32 * 0.5 * (1. - height);
         } else if dying.seconds_passed < 2. {
             transform.rotation = Quat::from_rotation_y(dying.seconds_passed * consts::TAU * 4.);
         } else {
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+use crate::levels::tiles::tile_defs::HazardType;
+use crate::levels::tiles::tilemap::TileMap;
+use crate::systems::death::cause::DeathCause;
+use crate::systems::death::death_anim::Dying;
+use crate::systems::enemy::spawner::Enemy;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::dimens::Dimens;
+use crate::systems::motion::structs::player::Player;
+
+/// Hazards kill both the player and enemies. Characters that are already dying are left alone.
+type CharacterFilter = (Or<(With<Player>, With<Enemy>)>, Without<Dying>);
+
+/// Attached to every tile that is deadly to the touch.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Hazard(pub HazardType);
+
+/// Kills any character that touches a hazard, either by standing inside it or by standing on top
+/// of it. The player dies, enemies are removed from the game.
+pub fn touch_hazards(
+    mut commands: Commands,
+    tile_map: Res<TileMap>,
+    query_hazards: Query<(&Coords, &Hazard)>,
+    query_characters: Query<(Entity, &Coords, Option<&Enemy>), CharacterFilter>,
+) {
+    for (character, coords, enemy) in query_characters.iter() {
+        let feet = Coords::new(
+            tile_map.world_bounds.wrapped(&coords.pos.append_y(-1)),
+            Dimens::new(coords.dimens.x, 1),
+        );
+        let touched = query_hazards
+            .iter()
+            .find(|(hazard, _)| hazard.overlaps(coords) || hazard.overlaps(&feet));
+        if let Some((_, Hazard(hazard_type))) = touched {
+            if enemy.is_some() {
+                commands.entity(character).despawn_recursive();
+            } else {
+                commands
+                    .entity(character)
+                    .insert(Dying::new(DeathCause::Hazard(*hazard_type)));
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::audio::sound_event::SoundEvent;
+use crate::config::settings::difficulty_settings::DifficultySettings;
+use crate::loading::assets::SoundType;
+use crate::systems::checkpoint::CheckpointSave;
+use crate::systems::death::cause::DeathLog;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::player::Player;
+
+/// A pickup that grants the player an extra life.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct ExtraLife;
+
+/// How many lives the player has left. Lives carry over from one level to the next, and are only
+/// refilled when the game starts and after a game over.
+#[derive(Debug, Default)]
+pub struct Lives {
+    /// The number of lives left. If this is None, lives are not limited at all.
+    remaining: Option<u32>,
+}
+
+impl Lives {
+    #[must_use]
+    pub fn new(settings: &DifficultySettings) -> Self {
+        Lives {
+            remaining: settings.limited_lives.then_some(settings.starting_lives),
+        }
+    }
+
+    /// The number of lives left, or None if lives are not limited.
+    #[must_use]
+    pub fn remaining(&self) -> Option<u32> {
+        self.remaining
+    }
+
+    pub fn lose_one(&mut self) {
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+
+    pub fn gain_one(&mut self) {
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining += 1;
+        }
+    }
+
+    /// True iff lives are limited and the player has none left.
+    #[must_use]
+    pub fn is_game_over(&self) -> bool {
+        self.remaining == Some(0)
+    }
+}
+
+/// The player picks up any extra life they touch.
+/// If lives are not limited, the pickup is simply removed.
+pub fn collect_extra_lives(
+    mut commands: Commands,
+    mut audio: EventWriter<SoundEvent>,
+    mut lives: ResMut<Lives>,
+    query_player: Query<&Coords, With<Player>>,
+    query_pickups: Query<(Entity, &Coords), With<ExtraLife>>,
+) {
+    for player in query_player.iter() {
+        for (entity, _) in query_pickups
+            .iter()
+            .filter(|(_, coords)| coords.overlaps(player))
+        {
+            audio.send(SoundEvent::Sfx(SoundType::PickupLastKey, false));
+            lives.gain_one();
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Gives the player a fresh set of lives and forgets about their previous deaths and checkpoints.
+pub fn refill_lives(
+    mut commands: Commands,
+    mut log: ResMut<DeathLog>,
+    mut checkpoint: ResMut<CheckpointSave>,
+    settings: Res<DifficultySettings>,
+) {
+    log.clear();
+    checkpoint.clear();
+    commands.insert_resource(Lives::new(&settings));
+}
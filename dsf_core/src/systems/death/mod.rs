@@ -1 +1,4 @@
+pub mod cause;
 pub mod death_anim;
+pub mod hazard;
+pub mod lives;
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+use crate::systems::death::cause::DeathCause;
 use crate::systems::death::death_anim::Dying;
 use crate::systems::enemy::spawner::Enemy;
 use crate::systems::motion::structs::coords::Coords;
@@ -13,7 +14,9 @@ pub fn enemy_kill(
     if let Ok((player, player_coords)) = query_player.get_single() {
         for enemy_coords in query_enemy.iter() {
             if enemy_coords.overlaps(player_coords) {
-                commands.entity(player).insert(Dying::default());
+                commands
+                    .entity(player)
+                    .insert(Dying::new(DeathCause::Enemy));
             }
         }
     }
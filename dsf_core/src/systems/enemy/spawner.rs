@@ -1,10 +1,12 @@
 use bevy::prelude::*;
 
 use crate::audio::sound_event::SoundEvent;
+use crate::levels::tiles::tile_defs::SpawnerDefinition;
 use crate::loading::assets::{AssetStorage, SoundType};
 use crate::loading::entities::inflate::spawn_enemy;
 use crate::systems::motion::structs::coords::Coords;
 use crate::systems::motion::structs::dimens::Dimens;
+use crate::systems::signals::{SignalChanged, SignalWire};
 
 const SPAWN_COOLDOWN: f32 = 2.;
 const SPAWN_DURATION: f32 = 2.;
@@ -12,12 +14,26 @@ const SPAWN_DURATION: f32 = 2.;
 #[derive(Component)]
 pub struct Spawner {
     pub state: SpawnerState,
+    /// A disabled spawner will not start spawning a new enemy. Spawners can be switched on and
+    /// off by wiring them to a signal.
+    pub enabled: bool,
+    /// The width and height of the enemies this spawner spawns.
+    pub enemy_dimens: Dimens,
 }
 
 impl Default for Spawner {
     fn default() -> Self {
+        Spawner::new(&SpawnerDefinition::default())
+    }
+}
+
+impl Spawner {
+    #[must_use]
+    pub fn new(definition: &SpawnerDefinition) -> Self {
         Spawner {
             state: SpawnerState::SpawnCooldown(Timer::from_seconds(SPAWN_COOLDOWN, false)),
+            enabled: true,
+            enemy_dimens: definition.enemy_dimens,
         }
     }
 }
@@ -42,7 +58,9 @@ pub fn activate_spawners(
     query_enemy: Query<Entity, With<Enemy>>,
 ) {
     for (spawner_coords, mut spawner, mut sprite) in query_spawner.iter_mut() {
+        let enabled = spawner.enabled;
         match &mut spawner.state {
+            SpawnerState::SpawnCooldown(_) if !enabled => (),
             SpawnerState::SpawnCooldown(timer) => {
                 timer.tick(time.delta());
                 if timer.finished() {
@@ -57,11 +75,11 @@ pub fn activate_spawners(
                 if timer.finished() {
                     audio.send(SoundEvent::Sfx(SoundType::SpawnerOpenClose, false));
                     sprite.index = 0;
-                    let entity = spawn_enemy(
-                        &mut commands,
-                        &storage,
-                        Coords::new(spawner_coords.pos, Dimens::new(2, 2)),
-                    );
+                    let dimens = spawner.enemy_dimens;
+                    let pos = spawner_coords
+                        .pos
+                        .append_x((spawner_coords.dimens.x - dimens.x) / 2);
+                    let entity = spawn_enemy(&mut commands, &storage, Coords::new(pos, dimens));
                     spawner.state = SpawnerState::Spawned(entity);
                 }
             }
@@ -74,3 +92,18 @@ pub fn activate_spawners(
         }
     }
 }
+
+/// Every time the signal a spawner is wired to switches on, that spawner is switched on or off.
+pub fn toggle_wired_spawners(
+    mut events: EventReader<SignalChanged>,
+    mut query: Query<(&mut Spawner, &SignalWire)>,
+) {
+    for event in events.iter().filter(|event| event.active) {
+        for (mut spawner, _) in query
+            .iter_mut()
+            .filter(|(_, wire)| wire.signal == event.signal)
+        {
+            spawner.enabled ^= true;
+        }
+    }
+}
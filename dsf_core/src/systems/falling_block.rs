@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+
+use crate::audio::sound_event::SoundEvent;
+use crate::config::movement_config::MovementConfig;
+use crate::levels::tiles::tile_defs::TileDefinition;
+use crate::levels::tiles::tilemap::TileMap;
+use crate::loading::assets::SoundType;
+use crate::systems::death::cause::DeathCause;
+use crate::systems::death::death_anim::Dying;
+use crate::systems::enemy::spawner::Enemy;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::player::Player;
+use crate::systems::motion::structs::pos::Pos;
+
+/// Falling blocks crush both the player and enemies.
+type CharacterFilter = (Or<(With<Player>, With<Enemy>)>, Without<FallingBlock>);
+
+type CharacterComponents<'a> = (Entity, &'a Coords, Option<&'a Enemy>, Option<&'a Dying>);
+
+#[derive(Component, Default)]
+pub struct FallingBlock {
+    /// Is only present while the block is falling.
+    fall: Option<Fall>,
+}
+
+/// Keeps track of a block while it is in free fall.
+struct Fall {
+    /// The tile definition key of the block, so it can be put back in the tile map when it lands.
+    key: String,
+    /// The anchored y-coordinate that the block had when it started falling.
+    starting_y_pos: f32,
+    /// The time in seconds since the block started falling.
+    duration: f32,
+}
+
+/// Lets blocks fall as soon as there is nothing left beneath them, for example because the player
+/// dug it away. A falling block is taken out of the tile map until it lands.
+///
+/// Any character that the block falls onto is crushed: the player dies and enemies are removed.
+/// Blocks fall at the same speed as characters do, as configured in the `MovementConfig`.
+pub fn drop_falling_blocks(
+    mut commands: Commands,
+    config: Res<MovementConfig>,
+    time: Res<Time>,
+    mut tile_map: ResMut<TileMap>,
+    mut audio: EventWriter<SoundEvent>,
+    mut query_blocks: Query<(&mut FallingBlock, &mut Coords, &mut Transform)>,
+    query_characters: Query<CharacterComponents, CharacterFilter>,
+) {
+    let bounds = tile_map.world_bounds;
+    for (mut block, mut coords, mut transform) in query_blocks.iter_mut() {
+        let fall = if let Some(fall) = block.fall.as_mut() {
+            fall
+        } else {
+            if is_supported(&coords, &tile_map) {
+                continue;
+            }
+            let key = tile_map.get_tile_def_key(&coords.pos).cloned();
+            tile_map.remove_tile(&coords.pos);
+            block.fall.insert(Fall {
+                key: key.unwrap_or_default(),
+                starting_y_pos: coords.pos.y as f32,
+                duration: 0.,
+            })
+        };
+        fall.duration += time.delta_seconds();
+        let mut anchored_y = fall.starting_y_pos + config.jump.fall_delta_y(fall.duration);
+        if coords.pos.y <= bounds.y() && anchored_y < bounds.y() as f32 {
+            // Fell through the bottom of the world, wrap around to the top.
+            coords.pos.y += bounds.height();
+            fall.starting_y_pos += bounds.height() as f32;
+            anchored_y += bounds.height() as f32;
+        }
+
+        // Move down one row at a time, so the block can never skip over the tile it should land on.
+        while anchored_y <= (coords.pos.y - 1) as f32 && !is_supported(&coords, &tile_map) {
+            coords.pos.y -= 1;
+        }
+        let landed = anchored_y <= coords.pos.y as f32 && is_supported(&coords, &tile_map);
+
+        let crush_zone = Coords::new(
+            bounds.wrapped(&Pos::new(coords.pos.x, anchored_y.floor() as i32)),
+            coords.dimens,
+        );
+        for (character, _, enemy, dying) in
+            query_characters.iter().filter(|(_, character, _, _)| {
+                character.overlaps(&crush_zone) || character.overlaps(&coords)
+            })
+        {
+            if enemy.is_some() {
+                commands.entity(character).despawn_recursive();
+            } else if dying.is_none() {
+                commands
+                    .entity(character)
+                    .insert(Dying::new(DeathCause::Crushed));
+            }
+        }
+
+        let (centered_x, centered_y) = coords.to_centered_coords(coords.pos);
+        transform.translation.x = centered_x;
+        if landed {
+            audio.send(SoundEvent::Sfx(SoundType::TrapWallCreated, false));
+            transform.translation.y = centered_y;
+            if let Some(fall) = block.fall.take() {
+                tile_map.put_tile(&coords.pos, coords.dimens, fall.key);
+            }
+        } else {
+            transform.translation.y = anchored_y + coords.dimens.y as f32 * 0.5;
+        }
+    }
+}
+
+/// True iff there is a tile in the row directly beneath the block that it can rest on. Tiles
+/// without collision, like keys and tools, do not hold it up.
+fn is_supported(coords: &Coords, tile_map: &TileMap) -> bool {
+    (0..coords.dimens.x).any(|x| {
+        tile_map
+            .get_tile(&Pos::new(coords.pos.x + x, coords.pos.y - 1))
+            .is_some_and(TileDefinition::provides_platform)
+    })
+}
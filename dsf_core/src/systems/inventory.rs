@@ -0,0 +1,223 @@
+use bevy::prelude::*;
+
+use crate::audio::sound_event::SoundEvent;
+use crate::config::settings::difficulty_settings::DifficultySettings;
+use crate::levels::tiles::objects::Tool;
+use crate::levels::tiles::tile_defs::ToolType;
+use crate::levels::tiles::tilemap::TileMap;
+use crate::loading::assets::{AssetStorage, SoundType};
+use crate::loading::entities::inflate::spawn_from_def;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::player::{EquippedTag, Player};
+use crate::systems::motion::structs::steering::Steering;
+
+/// A tool that the player is carrying.
+#[derive(Debug, Clone)]
+pub struct CarriedTool {
+    /// The key of the tool's tile definition. Needed to put the tool back into the world when the
+    /// player drops it.
+    pub key: String,
+    pub tool: Tool,
+}
+
+/// The tools that the player is carrying. Only the selected tool is in the player's hands; that
+/// is the tool that will be used. How many tools fit in the inventory is configured in the
+/// `DifficultySettings`.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Inventory {
+    pub tools: Vec<CarriedTool>,
+    /// Index of the tool in the player's hands. If this is None, the player's hands are empty,
+    /// even though they might still be carrying tools.
+    pub selected: Option<usize>,
+}
+
+impl Inventory {
+    /// Returns the tool in the player's hands, if any.
+    #[must_use]
+    pub fn equipped(&self) -> Option<&CarriedTool> {
+        self.selected.and_then(|index| self.tools.get(index))
+    }
+
+    /// Returns the type of the tool in the player's hands, if any.
+    #[must_use]
+    pub fn equipped_type(&self) -> Option<ToolType> {
+        self.equipped()
+            .map(|carried| carried.tool.tool_type.clone())
+    }
+
+    /// True iff there is room for the given number of tools.
+    #[must_use]
+    pub fn has_room(&self, slots: usize) -> bool {
+        self.tools.len() < slots.max(1)
+    }
+
+    /// Adds the tool to the inventory and puts it in the player's hands.
+    pub fn add(&mut self, tool: CarriedTool) {
+        self.tools.push(tool);
+        self.selected = Some(self.tools.len() - 1);
+    }
+
+    /// Removes the tool in the player's hands from the inventory, leaving the hands empty.
+    pub fn take_equipped(&mut self) -> Option<CarriedTool> {
+        let index = self.selected.take()?;
+        (index < self.tools.len()).then(|| self.tools.remove(index))
+    }
+
+    /// Wears down the tool in the player's hands by one use. If that breaks the tool, it is removed
+    /// from the inventory and returned.
+    pub fn wear_equipped(&mut self) -> Option<CarriedTool> {
+        let broke = self
+            .selected
+            .and_then(|index| self.tools.get_mut(index))
+            .is_some_and(|carried| carried.tool.use_once());
+        if broke {
+            self.take_equipped()
+        } else {
+            None
+        }
+    }
+
+    /// Puts the next tool in the player's hands. After the last tool, the player's hands are empty
+    /// until they select the next tool again.
+    pub fn select_next(&mut self) {
+        self.selected = match self.selected {
+            None if !self.tools.is_empty() => Some(0),
+            Some(index) if index + 1 < self.tools.len() => Some(index + 1),
+            _ => None,
+        };
+    }
+}
+
+/// Added to tools that the player just dropped, so they don't pick them right back up. It is
+/// removed once the player is no longer touching the tool.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Dropped;
+
+/// When the player presses E, they put the tool in their hands down at their feet. If they were
+/// standing on another tool, they will pick that one up instead, effectively swapping the two.
+///
+/// Tools can only be dropped while standing on solid ground, on a spot where nothing else blocks
+/// the tool.
+pub fn drop_tool(
+    mut commands: Commands,
+    mut audio: EventWriter<SoundEvent>,
+    storage: Res<AssetStorage>,
+    tile_map: Res<TileMap>,
+    mut keys: ResMut<Input<KeyCode>>,
+    mut query_player: Query<(&mut Inventory, &Steering, &Coords), With<Player>>,
+) {
+    if !keys.clear_just_pressed(KeyCode::E) {
+        return;
+    }
+    for (mut inventory, steering, player) in query_player.iter_mut() {
+        let dimens = inventory
+            .equipped()
+            .map(|carried| tile_map.tile_defs.get(&carried.key).dimens);
+        let drop_coords = dimens.map(|dimens| Coords::new(player.pos, dimens));
+        if !steering.is_grounded() || !drop_coords.is_some_and(|coords| is_free(&coords, &tile_map))
+        {
+            audio.send(SoundEvent::Sfx(SoundType::CannotPerformAction, false));
+            continue;
+        }
+        if let Some(carried) = inventory.take_equipped() {
+            audio.send(SoundEvent::Sfx(SoundType::PickupTool, false));
+            let tool_def = tile_map.tile_defs.get(&carried.key);
+            let entity =
+                spawn_from_def(&mut commands, &storage, player.pos, &carried.key, tool_def);
+            // Replace the pristine tool that was spawned from the definition with the worn one.
+            commands.entity(entity).insert(carried.tool).insert(Dropped);
+        }
+    }
+}
+
+/// In multi-slot inventory mode, pressing TAB puts the next tool in the player's hands.
+pub fn cycle_tools(
+    settings: Res<DifficultySettings>,
+    mut keys: ResMut<Input<KeyCode>>,
+    mut query_player: Query<&mut Inventory, With<Player>>,
+) {
+    if settings.inventory_slots > 1 && keys.clear_just_pressed(KeyCode::Tab) {
+        for mut inventory in query_player.iter_mut() {
+            inventory.select_next();
+        }
+    }
+}
+
+/// The color of the durability bar for a tool that is as good as new and one that is about to break.
+const DURABILITY_FULL: Color = Color::rgb(0.3, 0.9, 0.3);
+const DURABILITY_LOW: Color = Color::rgb(0.9, 0.2, 0.2);
+
+/// Whenever the inventory changes, shows the tool that is in the player's hands.
+/// Tools with multiple uses get a bar underneath that shows how many uses are left.
+pub fn show_equipped_tool(
+    mut commands: Commands,
+    storage: Res<AssetStorage>,
+    tile_map: Res<TileMap>,
+    query_player: Query<(Entity, &Inventory), Changed<Inventory>>,
+    query_equipped: Query<Entity, With<EquippedTag>>,
+) {
+    for (player, inventory) in query_player.iter() {
+        for entity in query_equipped.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        if let Some(carried) = inventory.equipped() {
+            let size = tile_map.tile_defs.get(&carried.key).dimens.as_vec2();
+            let equipped = commands
+                .spawn()
+                .insert(EquippedTag)
+                .insert_bundle(SpriteSheetBundle {
+                    texture_atlas: storage.get_atlas(&carried.tool.sprite),
+                    transform: Transform::from_xyz(0., 0., 0.),
+                    sprite: TextureAtlasSprite {
+                        index: carried.tool.sprite_nr,
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .id();
+            if let Some(condition) = carried.tool.condition() {
+                let bar = commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: durability_color(condition),
+                            custom_size: Some(Vec2::new(size.x * condition, 0.2)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(
+                            size.x * (condition - 1.) / 2.,
+                            -size.y / 2.,
+                            0.1,
+                        ),
+                        ..default()
+                    })
+                    .id();
+                commands.entity(equipped).push_children(&[bar]);
+            }
+            commands.entity(player).push_children(&[equipped]);
+        }
+    }
+}
+
+fn durability_color(condition: f32) -> Color {
+    let [full_r, full_g, full_b, _] = DURABILITY_FULL.as_rgba_f32();
+    let [low_r, low_g, low_b, _] = DURABILITY_LOW.as_rgba_f32();
+    Color::rgb(
+        low_r + (full_r - low_r) * condition,
+        low_g + (full_g - low_g) * condition,
+        low_b + (full_b - low_b) * condition,
+    )
+}
+
+/// True iff none of the positions covered by the given coords contain a tile that the tool would
+/// be stuck in.
+fn is_free(coords: &Coords, tile_map: &TileMap) -> bool {
+    (0..coords.dimens.x)
+        .flat_map(|x| (0..coords.dimens.y).map(move |y| (x, y)))
+        .map(|(x, y)| coords.pos.append_xy(x, y))
+        .all(|pos| {
+            !tile_map
+                .get_tile(&pos)
+                .is_some_and(|tile| tile.collides_horizontally() || tile.collides_bottom())
+        })
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+
+use crate::audio::sound_event::SoundEvent;
+use crate::levels::tiles::tile_defs::KeyColor;
+use crate::levels::tiles::tilemap::TileMap;
+use crate::loading::assets::SoundType;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::dimens::Dimens;
+use crate::systems::motion::structs::player::Player;
+
+/// A colored key that is lying somewhere in the level, waiting to be picked up.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ColoredKey(pub KeyColor);
+
+/// A door that only opens for a key of the same color.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LockedDoor(pub KeyColor);
+
+/// The colored keys that the player is carrying.
+#[derive(Component, Debug, Clone, Default)]
+pub struct KeyRing {
+    pub keys: Vec<KeyColor>,
+}
+
+impl KeyRing {
+    /// Removes a key of the given color from the key ring. Returns true if there was such a key.
+    pub fn take(&mut self, color: KeyColor) -> bool {
+        if let Some(index) = self.keys.iter().position(|key| *key == color) {
+            self.keys.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The player picks up any colored key they touch.
+pub fn pickup_colored_keys(
+    mut commands: Commands,
+    mut audio: EventWriter<SoundEvent>,
+    mut query_player: Query<(&Coords, &mut KeyRing), With<Player>>,
+    query_keys: Query<(Entity, &Coords, &ColoredKey)>,
+) {
+    for (player, mut key_ring) in query_player.iter_mut() {
+        for (entity, _, ColoredKey(color)) in query_keys
+            .iter()
+            .filter(|(_, coords, _)| coords.overlaps(player))
+        {
+            audio.send(SoundEvent::Sfx(SoundType::PickupKey, false));
+            key_ring.keys.push(*color);
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// When the player bumps into a locked door while carrying a key of the right color, the door
+/// opens and the key is used up. Until then, the door is as solid as any other block.
+pub fn unlock_doors(
+    mut commands: Commands,
+    mut audio: EventWriter<SoundEvent>,
+    mut tile_map: ResMut<TileMap>,
+    mut query_player: Query<(&Coords, &mut KeyRing), With<Player>>,
+    query_doors: Query<(Entity, &Coords, &LockedDoor)>,
+) {
+    for (player, mut key_ring) in query_player.iter_mut() {
+        // The door must be directly next to, above or below the player. Diagonals don't count.
+        let reach_horizontal = Coords::new(
+            player.pos.append_x(-1),
+            Dimens::new(player.dimens.x + 2, player.dimens.y),
+        );
+        let reach_vertical = Coords::new(
+            player.pos.append_y(-1),
+            Dimens::new(player.dimens.x, player.dimens.y + 2),
+        );
+        for (entity, door, LockedDoor(color)) in query_doors.iter() {
+            let within_reach = door.overlaps(&reach_horizontal) || door.overlaps(&reach_vertical);
+            if within_reach && key_ring.take(*color) {
+                audio.send(SoundEvent::Sfx(SoundType::SpawnerOpenClose, false));
+                tile_map.remove_tile(&door.pos);
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use iyes_loopless::state::NextState;
+
+use crate::audio::sound_event::SoundEvent;
+use crate::config::settings::debug_settings::DebugSettings;
+use crate::config::settings::progression::Progression;
+use crate::config::settings::user_cache::UserCache;
+use crate::level_select::structs::LevelSelectionInstruction;
+use crate::loading::assets::SoundType;
+use crate::states::AppState;
+use crate::systems::menu::button::{add_btn, MenuButtons};
+use crate::systems::motion::structs::pos::Pos;
+use crate::util::files::get_levels_dir;
+
+const BUTTON_RESTART: &str = "Restart";
+const BUTTON_MENU: &str = "Menu";
+
+pub fn setup_game_over(
+    mut commands: Commands,
+    mut audio: EventWriter<SoundEvent>,
+    assets: Res<AssetServer>,
+) {
+    audio.send(SoundEvent::Music(None));
+    audio.send(SoundEvent::KillAllSoundEffects);
+    audio.send(SoundEvent::Sfx(SoundType::GameOver, false));
+
+    commands.spawn_bundle(Camera2dBundle::default());
+    let text_style = TextStyle {
+        font: assets.load("fonts/square.ttf"),
+        font_size: 100.0,
+        color: Color::rgb(0.75, 0.1, 0.1),
+    };
+    commands.spawn_bundle(Text2dBundle {
+        text: Text::from_section("GAME OVER", text_style).with_alignment(TextAlignment::CENTER),
+        transform: Transform::from_xyz(0., 250., 0.),
+        ..default()
+    });
+
+    let buttons = vec![BUTTON_RESTART.to_string(), BUTTON_MENU.to_string()];
+    let container = commands
+        .spawn_bundle(SpatialBundle::from_transform(Transform::from_xyz(
+            0., -50., 0.,
+        )))
+        .id();
+    for i in 0..buttons.len() {
+        add_btn(&mut commands, &assets, &buttons, i, container);
+    }
+    commands.insert_resource(MenuButtons {
+        selected: 0,
+        buttons,
+        timer: None,
+    });
+}
+
+/// The player can either restart the adventure they were playing from the very beginning, or
+/// return to the menu.
+pub fn read_game_over_input(
+    mut commands: Commands,
+    mut keys: ResMut<Input<KeyCode>>,
+    mut buttons: ResMut<MenuButtons>,
+    mut progression: ResMut<Progression>,
+    mut instruction: ResMut<LevelSelectionInstruction>,
+    mut user_cache: ResMut<UserCache>,
+    config: Res<DebugSettings>,
+) {
+    if keys.any_just_pressed([KeyCode::Down, KeyCode::S]) {
+        buttons.selected =
+            (buttons.selected as i32 + 1).rem_euclid(buttons.buttons.len() as i32) as usize;
+    }
+    if keys.any_just_pressed([KeyCode::Up, KeyCode::W]) {
+        buttons.selected =
+            (buttons.selected as i32 - 1).rem_euclid(buttons.buttons.len() as i32) as usize;
+    }
+    let restart = if keys.clear_just_pressed(KeyCode::Escape) {
+        false
+    } else if keys.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter, KeyCode::Space]) {
+        keys.clear_just_pressed(KeyCode::Return);
+        keys.clear_just_pressed(KeyCode::NumpadEnter);
+        keys.clear_just_pressed(KeyCode::Space);
+        buttons.buttons.get(buttons.selected).unwrap() == BUTTON_RESTART
+    } else {
+        return;
+    };
+    let next = if config.use_alternate_menu {
+        if restart {
+            progression.reset();
+            instruction.level = Some(
+                get_levels_dir().join(progression.levels.get(progression.current_level).unwrap()),
+            );
+            AppState::InGame
+        } else {
+            AppState::AltMenu
+        }
+    } else if restart {
+        // Adventures are restarted by putting the player back at the start of the map.
+        if let Some(adventure) = instruction
+            .adventure
+            .as_ref()
+            .and_then(|path| path.file_name())
+        {
+            user_cache
+                .save_adventure_map_pos(adventure.to_string_lossy().to_string(), Pos::default());
+        }
+        AppState::LevelSelect
+    } else {
+        AppState::MainMenu
+    };
+    commands.insert_resource(NextState(next));
+}
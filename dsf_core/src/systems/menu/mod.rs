@@ -1,4 +1,5 @@
 pub mod alt_menu;
 pub mod button;
+pub mod game_over;
 pub mod main_menu;
 pub mod setup_hud;
@@ -1,15 +1,44 @@
 use bevy::prelude::*;
 use bevy::text::Text2dSize;
 
+use crate::config::settings::difficulty_settings::DifficultySettings;
 use crate::levels::tiles::tile_defs::DepthLayer;
 use crate::levels::tiles::tilemap::TileMap;
+use crate::levels::world_bounds::WorldBounds;
+use crate::loading::assets::{AssetStorage, SpriteType};
+use crate::systems::death::lives::Lives;
+use crate::systems::inventory::Inventory;
+use crate::systems::locks::KeyRing;
 use crate::systems::motion::structs::pos::Pos;
 
-pub fn setup_hud(mut commands: Commands, tile_map: Res<TileMap>, assets: Res<AssetServer>) {
+/// Index of the key sprite in the tools sprite sheet.
+const KEY_SPRITE_NR: usize = 6;
+
+/// Entities with this component are the icons in the HUD that show which colored keys the player
+/// is carrying.
+#[derive(Component, Debug, Default)]
+pub struct HeldKeyIcon;
+
+/// Entities with this component are the icons in the HUD that show which tools the player is
+/// carrying. Only used in multi-slot inventory mode.
+#[derive(Component, Debug, Default)]
+pub struct CarriedToolIcon;
+
+/// The text in the HUD that shows how many lives the player has left.
+#[derive(Component, Debug, Default)]
+pub struct LivesDisplay;
+
+pub fn setup_hud(
+    mut commands: Commands,
+    tile_map: Res<TileMap>,
+    lives: Res<Lives>,
+    settings: Res<DifficultySettings>,
+    assets: Res<AssetServer>,
+) {
     info!("setup_hud");
     let font = assets.load("fonts/square.ttf");
 
-    let pos = Pos::new(32, 23) + tile_map.world_bounds.pos;
+    let pos = hud_pos(&tile_map.world_bounds);
     spawn("MOVE:", pos, &mut commands, font.clone());
     spawn("ARROWS/WASD", pos.append_y(-1), &mut commands, font.clone());
 
@@ -19,7 +48,11 @@ pub fn setup_hud(mut commands: Commands, tile_map: Res<TileMap>, assets: Res<Ass
 
     let pos = pos.append_y(-3);
     spawn("USE TOOLS:", pos, &mut commands, font.clone());
-    spawn("SPACE", pos.append_y(-1), &mut commands, font.clone());
+    spawn("Q", pos.append_y(-1), &mut commands, font.clone());
+
+    let pos = pos.append_y(-3);
+    spawn("DROP TOOL:", pos, &mut commands, font.clone());
+    spawn("E", pos.append_y(-1), &mut commands, font.clone());
 
     let pos = pos.append_y(-3);
     spawn("RESET:", pos, &mut commands, font.clone());
@@ -27,10 +60,153 @@ pub fn setup_hud(mut commands: Commands, tile_map: Res<TileMap>, assets: Res<Ass
 
     let pos = pos.append_y(-3);
     spawn("BACK:", pos, &mut commands, font.clone());
-    spawn("ESC", pos.append_y(-1), &mut commands, font);
+    spawn("ESC", pos.append_y(-1), &mut commands, font.clone());
+
+    spawn(
+        "KEYS:",
+        held_keys_pos(&tile_map).append_y(1),
+        &mut commands,
+        font.clone(),
+    );
+
+    if let Some(remaining) = lives.remaining() {
+        let pos = held_keys_pos(&tile_map).append_y(-2);
+        spawn("LIVES:", pos, &mut commands, font.clone());
+        let text = spawn(
+            &remaining.to_string(),
+            pos.append_y(-1),
+            &mut commands,
+            font.clone(),
+        );
+        commands.entity(text).insert(LivesDisplay);
+    }
+
+    if settings.inventory_slots > 1 {
+        let pos = inventory_pos(&tile_map, &lives).append_y(1);
+        spawn("TOOLS (TAB):", pos, &mut commands, font);
+    }
+}
+
+/// Whenever the player's inventory changes, redraws the row of carried tools in the HUD. The tool
+/// in the player's hands is highlighted.
+pub fn show_inventory(
+    mut commands: Commands,
+    storage: Res<AssetStorage>,
+    tile_map: Res<TileMap>,
+    lives: Res<Lives>,
+    settings: Res<DifficultySettings>,
+    query_player: Query<&Inventory, Changed<Inventory>>,
+    query_icons: Query<Entity, With<CarriedToolIcon>>,
+) {
+    if settings.inventory_slots <= 1 {
+        return;
+    }
+    if let Ok(inventory) = query_player.get_single() {
+        for entity in query_icons.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        let pos = inventory_pos(&tile_map, &lives);
+        for (index, carried) in inventory.tools.iter().enumerate() {
+            let alpha = if inventory.selected == Some(index) {
+                1.
+            } else {
+                0.4
+            };
+            commands
+                .spawn_bundle(SpriteSheetBundle {
+                    texture_atlas: storage.get_atlas(&carried.tool.sprite),
+                    transform: Transform::from_xyz(
+                        pos.x as f32 + 0.5 + index as f32,
+                        pos.y as f32,
+                        DepthLayer::UiElements.z(),
+                    ),
+                    sprite: TextureAtlasSprite {
+                        index: carried.tool.sprite_nr,
+                        color: Color::rgba(1., 1., 1., alpha),
+                        custom_size: Some(Vec2::new(1., 1.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(CarriedToolIcon);
+        }
+    }
+}
+
+/// Keeps the number of lives in the HUD up to date.
+pub fn show_lives(lives: Res<Lives>, mut query: Query<&mut Text, With<LivesDisplay>>) {
+    if !lives.is_changed() {
+        return;
+    }
+    if let Some(remaining) = lives.remaining() {
+        for mut text in query.iter_mut() {
+            text.sections[0].value = remaining.to_string();
+        }
+    }
+}
+
+/// Whenever the player picks up or uses a colored key, redraws the row of keys in the HUD.
+pub fn show_held_keys(
+    mut commands: Commands,
+    storage: Res<AssetStorage>,
+    tile_map: Res<TileMap>,
+    query_player: Query<&KeyRing, Changed<KeyRing>>,
+    query_icons: Query<Entity, With<HeldKeyIcon>>,
+) {
+    if let Ok(key_ring) = query_player.get_single() {
+        for entity in query_icons.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        let pos = held_keys_pos(&tile_map);
+        for (index, color) in key_ring.keys.iter().enumerate() {
+            commands
+                .spawn_bundle(SpriteSheetBundle {
+                    texture_atlas: storage.get_atlas(&SpriteType::Tools),
+                    transform: Transform::from_xyz(
+                        pos.x as f32 + 0.5 + index as f32,
+                        pos.y as f32,
+                        DepthLayer::UiElements.z(),
+                    ),
+                    sprite: TextureAtlasSprite {
+                        index: KEY_SPRITE_NR,
+                        color: color.color(),
+                        custom_size: Some(Vec2::new(1., 1.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(HeldKeyIcon);
+        }
+    }
+}
+
+/// How wide the lines of text in the HUD are, in tiles.
+const HUD_WIDTH: f32 = 5.5;
+
+/// The top-left position of the HUD, just to the right of the level.
+fn hud_pos(world_bounds: &WorldBounds) -> Pos {
+    Pos::new(32, 23) + world_bounds.pos
+}
+
+/// The x-coordinate of the right edge of the HUD, which is drawn next to the level.
+#[must_use]
+pub fn hud_right_edge(world_bounds: &WorldBounds) -> f32 {
+    hud_pos(world_bounds).x as f32 + HUD_WIDTH
+}
+
+/// The position of the row of colored keys the player is carrying.
+fn held_keys_pos(tile_map: &TileMap) -> Pos {
+    hud_pos(&tile_map.world_bounds).append_y(-19)
+}
+
+/// The position of the row of tools the player is carrying. It goes below the number of lives, if
+/// that is shown.
+fn inventory_pos(tile_map: &TileMap, lives: &Lives) -> Pos {
+    let offset = if lives.remaining().is_some() { -6 } else { -3 };
+    held_keys_pos(tile_map).append_y(offset)
 }
 
-fn spawn(phrase: &str, pos: Pos, commands: &mut Commands, font: Handle<Font>) {
+fn spawn(phrase: &str, pos: Pos, commands: &mut Commands, font: Handle<Font>) -> Entity {
     let text_style = TextStyle {
         font,
         font_size: 60.,
@@ -43,12 +219,15 @@ fn spawn(phrase: &str, pos: Pos, commands: &mut Commands, font: Handle<Font>) {
     let transform =
         Transform::from_xyz(pos.x as f32 + 0.5, pos.y as f32, DepthLayer::UiElements.z())
             .with_scale(Vec3::new(1. / 128., 1. / 128., 1.));
-    commands.spawn().insert_bundle(Text2dBundle {
-        text: Text::from_section(phrase, text_style).with_alignment(text_alignment),
-        text_2d_size: Text2dSize {
-            size: Vec2::new(5., 1.),
-        },
-        transform,
-        ..default()
-    });
+    commands
+        .spawn()
+        .insert_bundle(Text2dBundle {
+            text: Text::from_section(phrase, text_style).with_alignment(text_alignment),
+            text_2d_size: Text2dSize {
+                size: Vec2::new(5., 1.),
+            },
+            transform,
+            ..default()
+        })
+        .id()
 }
@@ -1,13 +1,21 @@
 pub mod animations;
 pub mod background_anim;
 pub mod check_input;
+pub mod checkpoint;
+pub mod crumbling_floor;
 pub mod death;
 pub mod debug;
 pub mod enemy;
+pub mod falling_block;
+pub mod inventory;
+pub mod locks;
 pub mod menu;
 pub mod motion;
+pub mod platform;
 pub mod revolving_door;
 pub mod rewind;
+pub mod scoring;
+pub mod signals;
 pub mod tools;
 pub mod trap_wall;
 pub mod win_checking;
@@ -39,16 +39,22 @@ pub fn set_enemy_steering_intent(
     query_player: Query<&Coords, With<Player>>,
 ) {
     for (mut ai, mut intent, steering, coords) in query_enemy.iter_mut() {
+        if let Some(target) = intent.forced_walk {
+            intent.walk = Direction1D::new((target.x - coords.pos.x) as f32);
+            continue;
+        }
         match &mut ai.state {
             AiState::MakeNewPlan(timer) => {
                 timer.tick(time.delta());
                 if timer.finished() {
                     let right_is_blocked = any_collide(
                         &tiles_to_side(&Direction1D::Positive, coords, &tile_map.world_bounds),
+                        &Direction1D::Positive,
                         &tile_map,
                     );
                     let left_is_blocked = any_collide(
                         &tiles_to_side(&Direction1D::Negative, coords, &tile_map.world_bounds),
+                        &Direction1D::Negative,
                         &tile_map,
                     );
                     let player_pos = query_player
@@ -79,7 +85,7 @@ pub fn set_enemy_steering_intent(
             }
             AiState::Walking => {
                 let tiles = tiles_to_side(&steering.facing.x, coords, &tile_map.world_bounds);
-                if any_collide(&tiles, &tile_map) {
+                if any_collide(&tiles, &steering.facing.x, &tile_map) {
                     ai.state = AiState::MakeNewPlan(Timer::from_seconds(COOLDOWN, false));
                     intent.walk = Direction1D::Neutral;
                 }
@@ -111,10 +117,19 @@ fn tiles_to_side(facing: &Direction1D, coords: &Coords, bounds: &WorldBounds) ->
         .collect()
 }
 
-fn any_collide(blocks: &[Pos], tile_map: &TileMap) -> bool {
+/// True if any of the given blocks would stop an enemy walking in the given direction.
+/// Revolving doors that can be passed in that direction do not count, the enemy can walk into
+/// them to make them turn.
+fn any_collide(blocks: &[Pos], direction: &Direction1D, tile_map: &TileMap) -> bool {
     blocks.iter().any(|pos| {
-        tile_map
-            .get_tile(pos)
-            .map_or(false, TileDefinition::collides_horizontally)
+        tile_map.get_tile(pos).is_some_and(|tile_def| {
+            tile_def.collides_horizontally() && !is_passable_door(tile_def, direction)
+        })
     })
 }
+
+fn is_passable_door(tile_def: &TileDefinition, direction: &Direction1D) -> bool {
+    tile_def
+        .revolving()
+        .is_some_and(|door| &door.facing == direction)
+}
@@ -72,7 +72,7 @@ pub fn set_player_steering_intent(
             intent.walk_invalidated = false;
         }
         intent.climb = new_climb;
-        intent.jump = player.equipped.is_none() && initiate_jump;
+        intent.jump = initiate_jump;
         intent.jump_direction = if player.jump_grace_timer.is_some() {
             intent.walk
         } else {
@@ -4,10 +4,20 @@ use crate::config::movement_config::MovementConfig;
 use crate::systems::death::death_anim::Dying;
 use crate::systems::motion::structs::coords::Coords;
 use crate::systems::motion::structs::direction::Direction1D;
+use crate::systems::motion::structs::jump_arc::JumpArc;
 use crate::systems::motion::structs::steering::Steering;
 use crate::systems::motion::structs::steering_mode::SteeringMode;
 use crate::systems::motion::structs::velocity::Velocity;
 
+type MovementComponents<'a> = (
+    &'a mut Transform,
+    &'a mut TextureAtlasSprite,
+    &'a Steering,
+    &'a Coords,
+    &'a mut Velocity,
+    Option<&'a JumpArc>,
+);
+
 /// For every entity with a velocity and a transform, updates the transform according to the
 /// velocity.
 pub fn velocity_system(
@@ -23,18 +33,10 @@ pub fn velocity_system(
 /// Sets velocity for all entities with steering.
 pub fn movement_system(
     config: Res<MovementConfig>,
-    mut query: Query<
-        (
-            &mut Transform,
-            &mut TextureAtlasSprite,
-            &Steering,
-            &Coords,
-            &mut Velocity,
-        ),
-        Without<Dying>,
-    >,
+    mut query: Query<MovementComponents, Without<Dying>>,
 ) {
-    for (mut transform, mut sprite, steering, coords, mut velocity) in query.iter_mut() {
+    for (mut transform, mut sprite, steering, coords, mut velocity, arc) in query.iter_mut() {
+        let arc = arc.unwrap_or(&config.jump);
         // Flip sprite if character is facing left:
         sprite.flip_x = steering.facing.x == Direction1D::Positive;
 
@@ -66,7 +68,8 @@ pub fn movement_system(
             } => {
                 // Set y-position directly, based on movement function. We don't use velocity for this.
                 velocity.y = 0.0;
-                transform.translation.y = starting_y_pos + steering.mode.calc_delta_y(duration);
+                transform.translation.y =
+                    starting_y_pos + steering.mode.calc_delta_y(duration, arc);
             }
             SteeringMode::Jumping {
                 starting_y_pos,
@@ -75,7 +78,8 @@ pub fn movement_system(
             } => {
                 // Set y-position directly, based on movement function. We don't use velocity for this.
                 velocity.y = 0.0;
-                transform.translation.y = starting_y_pos + steering.mode.calc_delta_y(duration);
+                transform.translation.y =
+                    starting_y_pos + steering.mode.calc_delta_y(duration, arc);
             }
         }
 
@@ -1,246 +1,382 @@
-use bevy::prelude::{EventWriter, Query, Res, ResMut, Time, Transform, Without};
+use bevy::prelude::{
+    Commands, DespawnRecursiveExt, Entity, EventWriter, Query, Res, ResMut, Time, Transform, Vec2,
+    Without,
+};
 
 use crate::audio::sound_event::SoundEvent;
+use crate::config::movement_config::MovementConfig;
 use crate::levels::tiles::tile_defs::TileDefinition;
 use crate::levels::tiles::tilemap::TileMap;
 use crate::levels::world_bounds::WorldBounds;
 use crate::loading::assets::SoundType;
+use crate::systems::death::cause::DeathCause;
 use crate::systems::death::death_anim::Dying;
+use crate::systems::enemy::spawner::Enemy;
 use crate::systems::motion::structs::coords::Coords;
 use crate::systems::motion::structs::direction::{Direction1D, Direction2D};
+use crate::systems::motion::structs::jump_arc::JumpArc;
 use crate::systems::motion::structs::pos::Pos;
 use crate::systems::motion::structs::steering::Steering;
 use crate::systems::motion::structs::steering_intent::SteeringIntent;
 use crate::systems::motion::structs::steering_mode::SteeringMode;
 use crate::systems::rewind::structs::{Frame, History};
 
+type SteeringComponents<'a> = (
+    Entity,
+    &'a mut SteeringIntent,
+    &'a mut Transform,
+    &'a mut Steering,
+    &'a mut Coords,
+    Option<&'a Enemy>,
+    Option<&'a JumpArc>,
+);
+
+/// Everything that the steering decisions depend on, apart from the entity itself.
+pub struct SteeringContext<'a> {
+    pub tile_map: &'a TileMap,
+    pub config: &'a MovementConfig,
+    pub arc: &'a JumpArc,
+    /// Seconds since the previous frame.
+    pub delta_seconds: f32,
+    /// If true, a frame is pushed onto the history even if the entity didn't change position.
+    pub force_key_frame: bool,
+}
+
+/// Something that happened while steering an entity, that the steering system needs to act upon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SteeringEffect {
+    Sound(SoundType),
+    /// The entity landed after falling further than is safe.
+    FellToDeath,
+    /// The entity's position should be recorded in the history.
+    PushFrame(Pos),
+}
+
+/// The result of steering an entity for a single frame.
+#[derive(Debug, Clone)]
+pub struct SteeringUpdate {
+    pub intent: SteeringIntent,
+    pub steering: Steering,
+    pub coords: Coords,
+    /// The entity's translation. It only differs from the old translation if the entity wrapped
+    /// around the edge of the world.
+    pub translation: Vec2,
+    pub effects: Vec<SteeringEffect>,
+}
+
+/// Applies the steering decisions to every entity with steering. All decisions are made by
+/// `steer`; this system only applies the results.
 pub fn steering_system(
+    mut commands: Commands,
+    config: Res<MovementConfig>,
     tile_map: Res<TileMap>,
     mut history: ResMut<History>,
     time: Res<Time>,
     mut audio: EventWriter<SoundEvent>,
-    mut query: Query<
-        (
-            &mut SteeringIntent,
-            &mut Transform,
-            &mut Steering,
-            &mut Coords,
-        ),
-        Without<Dying>,
-    >,
+    mut query: Query<SteeringComponents, Without<Dying>>,
 ) {
-    for (mut intent, mut transform, mut steering, mut coords) in query.iter_mut() {
-        let old_pos = coords.pos;
-        let (anchored_x, anchored_y) = coords.to_anchor_coords(&transform);
-        coords.pos = Pos::new(anchored_x.round() as i32, anchored_y.round() as i32);
-        wrap(
-            &tile_map.world_bounds,
-            &mut steering,
-            &mut coords,
-            &mut transform,
+    for (entity, mut intent, mut transform, mut steering, mut coords, enemy, arc) in
+        query.iter_mut()
+    {
+        let context = SteeringContext {
+            tile_map: &tile_map,
+            config: &config,
+            arc: arc.unwrap_or(&config.jump),
+            delta_seconds: time.delta_seconds(),
+            force_key_frame: history.force_key_frame,
+        };
+        let update = steer(
+            &intent,
+            &steering,
+            &coords,
+            transform.translation.truncate(),
+            &context,
         );
-
-        if steering.is_mid_air() {
-            steering.mode = steering.mode.add_to_duration(time.delta_seconds());
+        *intent = update.intent;
+        *steering = update.steering;
+        *coords = update.coords;
+        transform.translation.x = update.translation.x;
+        transform.translation.y = update.translation.y;
+        for effect in update.effects {
+            match effect {
+                SteeringEffect::Sound(sound) => audio.send(SoundEvent::Sfx(sound, false)),
+                SteeringEffect::FellToDeath if enemy.is_some() => {
+                    commands.entity(entity).despawn_recursive();
+                }
+                SteeringEffect::FellToDeath => {
+                    commands.entity(entity).insert(Dying::new(DeathCause::Fall));
+                }
+                SteeringEffect::PushFrame(pos) => history.push_frame(Frame::new(pos)),
+            }
         }
+    }
+}
 
-        if steering.is_grounded() && !intent.face.is_neutral() {
-            steering.facing.x = intent.face;
-        }
+/// Decides how an entity moves this frame, based on what it wants to do (the intent), what it was
+/// doing (the steering), where it is and what the level around it looks like.
+///
+/// The translation is the center point of the entity. This function has no side effects; it
+/// returns the new state of the entity along with everything the caller should act upon.
+#[must_use]
+pub fn steer(
+    intent: &SteeringIntent,
+    steering: &Steering,
+    coords: &Coords,
+    translation: Vec2,
+    context: &SteeringContext,
+) -> SteeringUpdate {
+    let mut intent = intent.clone();
+    let mut steering = steering.clone();
+    let mut coords = *coords;
+    let mut translation = translation;
+    let mut effects = Vec::new();
 
-        // The following if-else construction checks if the steering mode should be changed.
-        let has_ground_beneath_feet = is_grounded(&coords, &tile_map);
-        if steering.is_falling()
-            && anchored_y <= coords.pos.y as f32
-            && has_ground_beneath_feet
-            && on_solid_ground(&coords, &tile_map)
-        {
-            // If falling and you reached the floor, set to grounded.
-            steering.mode = SteeringMode::Grounded;
-            steering.destination = coords.pos;
-        } else if (steering.is_grounded()
-            && !has_ground_beneath_feet
-            && aligned_with_grid(steering.destination.x as f32, anchored_x, intent.walk))
-            || (steering.is_climbing() && intent.jump)
-        {
-            steering.mode = SteeringMode::Falling {
-                x_movement: Direction1D::Neutral,
-                starting_y_pos: transform.translation.y,
+    let old_pos = coords.pos;
+    let anchored_x = translation.x - 0.5 * coords.dimens.x as f32;
+    let anchored_y = translation.y - 0.5 * coords.dimens.y as f32;
+    coords.pos = Pos::new(anchored_x.round() as i32, anchored_y.round() as i32);
+    wrap(
+        &context.tile_map.world_bounds,
+        &mut steering,
+        &mut coords,
+        &mut translation,
+    );
+
+    if steering.is_mid_air() {
+        steering.mode = steering.mode.add_to_duration(context.delta_seconds);
+    }
+
+    if steering.is_grounded() && !intent.face.is_neutral() {
+        steering.facing.x = intent.face;
+    }
+
+    // A jump that cannot be performed right away is remembered for a little while.
+    steering.jump_buffer = if intent.jump {
+        Some(0.)
+    } else {
+        steering
+            .jump_buffer
+            .map(|time_passed| time_passed + context.delta_seconds)
+            .filter(|time_passed| *time_passed <= context.config.jump_buffer)
+    };
+    let wants_to_jump = steering.jump_buffer.is_some();
+
+    // The following if-else construction checks if the steering mode should be changed.
+    let has_ground_beneath_feet = is_grounded(&coords, context.tile_map);
+    if steering.is_falling()
+        && anchored_y <= coords.pos.y as f32
+        && has_ground_beneath_feet
+        && on_solid_ground(&coords, context.tile_map)
+    {
+        // If falling and you reached the floor, set to grounded.
+        if let SteeringMode::Falling { starting_y_pos, .. } = steering.mode {
+            let fall_height = starting_y_pos - translation.y;
+            if context
+                .config
+                .max_safe_fall_height
+                .is_some_and(|max_height| fall_height > max_height)
+            {
+                effects.push(SteeringEffect::FellToDeath);
+            }
+        }
+        steering.mode = SteeringMode::Grounded;
+        steering.destination = coords.pos;
+        steering.walked_off_ledge = false;
+    } else if (steering.is_grounded()
+        && !has_ground_beneath_feet
+        && aligned_with_grid(steering.destination.x as f32, anchored_x, intent.walk))
+        || (steering.is_climbing() && wants_to_jump)
+    {
+        steering.walked_off_ledge = steering.is_grounded();
+        if steering.is_climbing() {
+            steering.jump_buffer = None;
+        }
+        steering.mode = SteeringMode::Falling {
+            x_movement: Direction1D::Neutral,
+            starting_y_pos: translation.y,
+            duration: 0.,
+        };
+    } else if wants_to_jump
+        && (steering.is_grounded() || steering.within_coyote_time(context.config.coyote_time))
+    {
+        steering.jump_buffer = None;
+        // A jump in coyote time starts from the ledge that was walked off, not from wherever the
+        // character has fallen to since.
+        let mut ledge = (translation, coords);
+        if let SteeringMode::Falling { starting_y_pos, .. } = steering.mode {
+            ledge.0.y = starting_y_pos;
+            ledge.1.pos.y = (starting_y_pos - 0.5 * coords.dimens.y as f32).round() as i32;
+        }
+        if is_underneath_ceiling(&ledge.1, context.tile_map) {
+            effects.push(SteeringEffect::Sound(SoundType::CannotPerformAction));
+        } else {
+            effects.push(SteeringEffect::Sound(SoundType::Jump));
+            (translation, coords) = ledge;
+            steering.walked_off_ledge = false;
+            steering.mode = SteeringMode::Jumping {
+                x_movement: intent.face,
+                starting_y_pos: translation.y,
                 duration: 0.,
             };
-        } else if steering.is_grounded() && intent.jump {
-            if is_underneath_ceiling(&coords, &tile_map) {
-                audio.send(SoundEvent::Sfx(SoundType::CannotPerformAction, false));
-            } else {
-                audio.send(SoundEvent::Sfx(SoundType::Jump, false));
-                steering.mode = SteeringMode::Jumping {
-                    x_movement: intent.face,
-                    starting_y_pos: transform.translation.y,
-                    duration: 0.,
-                };
-            }
-        } else if steering.jump_has_peaked() {
-            steering.mode = steering.mode.jump_to_fall();
-        } else if steering.is_grounded()
-            && aligned_with_grid(steering.destination.x as f32, anchored_x, intent.walk)
-            && ((intent.climb.is_positive() && can_climb_up(&coords, &tile_map))
-                || (intent.climb.is_negative() && can_climb_down(&coords, &tile_map)))
-        {
-            steering.mode = SteeringMode::Climbing;
-            if !intent.walk.is_neutral() {
-                intent.walk_invalidated = true;
-            }
-        } else if steering.is_climbing()
-            && aligned_with_grid(steering.destination.y as f32, anchored_y, intent.climb)
-            && !intent.walk_invalidated
-            && ((intent.walk.is_positive()
-                && !is_against_wall_right(&coords, coords.pos.y as f32, &tile_map))
-                || (intent.walk.is_negative()
-                    && !is_against_wall_left(&coords, coords.pos.y as f32, &tile_map)))
-        {
-            steering.mode = SteeringMode::Grounded;
         }
+    } else if steering.jump_has_peaked(context.arc) {
+        steering.mode = steering.mode.jump_to_fall(context.arc);
+    } else if steering.is_grounded()
+        && aligned_with_grid(steering.destination.x as f32, anchored_x, intent.walk)
+        && ((intent.climb.is_positive() && can_climb_up(&coords, context.tile_map))
+            || (intent.climb.is_negative() && can_climb_down(&coords, context.tile_map)))
+    {
+        steering.mode = SteeringMode::Climbing;
+        if !intent.walk.is_neutral() {
+            intent.walk_invalidated = true;
+        }
+    } else if steering.is_climbing()
+        && aligned_with_grid(steering.destination.y as f32, anchored_y, intent.climb)
+        && !intent.walk_invalidated
+        && ((intent.walk.is_positive()
+            && !is_against_wall_right(&coords, coords.pos.y as f32, context.tile_map))
+            || (intent.walk.is_negative()
+                && !is_against_wall_left(&coords, coords.pos.y as f32, context.tile_map)))
+    {
+        steering.mode = SteeringMode::Grounded;
+    }
 
-        // This match will adjust the steering based on the current steering mode.
-        match steering.mode {
-            SteeringMode::Grounded => {
-                if !intent.walk.is_neutral() {
-                    steering.facing = Direction2D::from(intent.walk, Direction1D::Neutral);
-                    let offset_from_destination = steering.destination.x as f32 - anchored_x;
-                    if offset_from_destination < f32::EPSILON && intent.walk.is_positive() {
-                        if !is_against_wall_right(&coords, coords.pos.y as f32, &tile_map) {
-                            steering.destination.x = coords.pos.x + 1;
-                            audio.send(SoundEvent::Sfx(SoundType::Step, false));
-                        }
-                    } else if offset_from_destination > -f32::EPSILON && intent.walk.is_negative() {
-                        if !is_against_wall_left(&coords, coords.pos.y as f32, &tile_map) {
-                            steering.destination.x = coords.pos.x - 1;
-                            audio.send(SoundEvent::Sfx(SoundType::Step, false));
-                        }
-                    } else if !intent
-                        .walk
-                        .aligns_with((steering.destination.x - coords.pos.x) as f32)
-                    {
-                        // TODO: Maybe remove, this doesn't seem to do anything.
-                        // Player wants to go back where they came from.
-                        steering.destination.x = coords.pos.x;
+    // This match will adjust the steering based on the current steering mode.
+    match steering.mode {
+        SteeringMode::Grounded => {
+            if !intent.walk.is_neutral() {
+                steering.facing = Direction2D::from(intent.walk, Direction1D::Neutral);
+                let offset_from_destination = steering.destination.x as f32 - anchored_x;
+                if offset_from_destination < f32::EPSILON && intent.walk.is_positive() {
+                    if !is_against_wall_right(&coords, coords.pos.y as f32, context.tile_map) {
+                        steering.destination.x = coords.pos.x + 1;
+                        effects.push(SteeringEffect::Sound(SoundType::Step));
                     }
-                }
-            }
-            SteeringMode::Climbing => {
-                if !intent.climb.is_neutral() {
-                    steering.facing = Direction2D::from(Direction1D::Neutral, intent.climb);
-                    let offset_from_discrete_pos = steering.destination.y as f32 - anchored_y;
-                    if offset_from_discrete_pos < f32::EPSILON && intent.climb.is_positive() {
-                        if can_climb_up(&coords, &tile_map) {
-                            audio.send(SoundEvent::Sfx(SoundType::LadderStep, false));
-                            steering.destination.y = coords.pos.y + 1;
-                        } else {
-                            steering.mode = SteeringMode::Grounded;
-                        }
-                    } else if offset_from_discrete_pos > -f32::EPSILON && intent.climb.is_negative()
-                    {
-                        if can_climb_down(&coords, &tile_map) {
-                            audio.send(SoundEvent::Sfx(SoundType::LadderStep, false));
-                            steering.destination.y = coords.pos.y - 1;
-                        } else if above_air(&coords, &tile_map) {
-                            steering.mode = SteeringMode::Falling {
-                                x_movement: Direction1D::Neutral,
-                                starting_y_pos: transform.translation.y,
-                                duration: 0.,
-                            };
-                        } else {
-                            steering.mode = SteeringMode::Grounded;
-                        }
-                    } else if !intent
-                        .climb
-                        .aligns_with((steering.destination.y - coords.pos.y) as f32)
-                    {
-                        // TODO: Maybe remove, this doesn't seem to do anything.
-                        // Player wants to go back where they came from.
-                        steering.destination.y = coords.pos.y;
+                } else if offset_from_destination > -f32::EPSILON && intent.walk.is_negative() {
+                    if !is_against_wall_left(&coords, coords.pos.y as f32, context.tile_map) {
+                        steering.destination.x = coords.pos.x - 1;
+                        effects.push(SteeringEffect::Sound(SoundType::Step));
                     }
+                } else if !intent
+                    .walk
+                    .aligns_with((steering.destination.x - coords.pos.x) as f32)
+                {
+                    // TODO: Maybe remove, this doesn't seem to do anything.
+                    // Player wants to go back where they came from.
+                    steering.destination.x = coords.pos.x;
                 }
             }
-            SteeringMode::Falling {
-                x_movement,
-                starting_y_pos,
-                duration,
-            } => {
-                if x_movement.is_neutral() {
-                    // No horizontal movement.
-                    steering.destination.x = coords.pos.x;
-                } else if x_movement.is_positive() {
-                    // Moving towards the right.
-                    if is_against_wall_right(&coords, anchored_y, &tile_map) {
-                        steering.mode = SteeringMode::Falling {
-                            x_movement: Direction1D::Neutral,
-                            starting_y_pos,
-                            duration,
-                        };
-                    } else if aligned_with_grid(
-                        steering.destination.x as f32,
-                        anchored_x,
-                        x_movement,
-                    ) {
-                        steering.destination.x = coords.pos.x + 1;
+        }
+        SteeringMode::Climbing => {
+            if !intent.climb.is_neutral() {
+                steering.facing = Direction2D::from(Direction1D::Neutral, intent.climb);
+                let offset_from_discrete_pos = steering.destination.y as f32 - anchored_y;
+                if offset_from_discrete_pos < f32::EPSILON && intent.climb.is_positive() {
+                    if can_climb_up(&coords, context.tile_map) {
+                        effects.push(SteeringEffect::Sound(SoundType::LadderStep));
+                        steering.destination.y = coords.pos.y + 1;
+                    } else {
+                        steering.mode = SteeringMode::Grounded;
                     }
-                } else {
-                    // Moving towards the left.
-                    if is_against_wall_left(&coords, anchored_y, &tile_map) {
+                } else if offset_from_discrete_pos > -f32::EPSILON && intent.climb.is_negative() {
+                    if can_climb_down(&coords, context.tile_map) {
+                        effects.push(SteeringEffect::Sound(SoundType::LadderStep));
+                        steering.destination.y = coords.pos.y - 1;
+                    } else if above_air(&coords, context.tile_map) {
                         steering.mode = SteeringMode::Falling {
                             x_movement: Direction1D::Neutral,
-                            starting_y_pos,
-                            duration,
+                            starting_y_pos: translation.y,
+                            duration: 0.,
                         };
-                    } else if aligned_with_grid(
-                        steering.destination.x as f32,
-                        anchored_x,
-                        x_movement,
-                    ) {
-                        steering.destination.x = coords.pos.x - 1;
+                    } else {
+                        steering.mode = SteeringMode::Grounded;
                     }
+                } else if !intent
+                    .climb
+                    .aligns_with((steering.destination.y - coords.pos.y) as f32)
+                {
+                    // TODO: Maybe remove, this doesn't seem to do anything.
+                    // Player wants to go back where they came from.
+                    steering.destination.y = coords.pos.y;
                 }
             }
-            SteeringMode::Jumping {
-                x_movement,
-                starting_y_pos,
-                duration,
-            } => {
-                if !intent.jump_direction.is_neutral() {
-                    steering.mode = SteeringMode::Jumping {
-                        x_movement: intent.jump_direction,
+        }
+        SteeringMode::Falling {
+            x_movement,
+            starting_y_pos,
+            duration,
+        } => {
+            if x_movement.is_neutral() {
+                // No horizontal movement.
+                steering.destination.x = coords.pos.x;
+            } else if x_movement.is_positive() {
+                // Moving towards the right.
+                if is_against_wall_right(&coords, anchored_y, context.tile_map) {
+                    steering.mode = SteeringMode::Falling {
+                        x_movement: Direction1D::Neutral,
                         starting_y_pos,
                         duration,
                     };
-                    steering.facing =
-                        Direction2D::from(intent.jump_direction, Direction1D::Neutral);
+                } else if aligned_with_grid(steering.destination.x as f32, anchored_x, x_movement) {
+                    steering.destination.x = coords.pos.x + 1;
                 }
-                if x_movement.is_neutral() {
-                    // No horizontal movement.
-                    steering.destination.x = coords.pos.x;
-                } else if x_movement.is_positive() {
-                    // Moving towards the right.
-                    if aligned_with_grid(steering.destination.x as f32, anchored_x, x_movement)
-                        && !is_against_wall_right(&coords, coords.pos.y as f32, &tile_map)
-                    {
-                        steering.destination.x = coords.pos.x + 1;
-                    }
-                } else {
-                    // Moving towards the left.
-                    if aligned_with_grid(steering.destination.x as f32, anchored_x, x_movement)
-                        && !is_against_wall_left(&coords, coords.pos.y as f32, &tile_map)
-                    {
-                        steering.destination.x = coords.pos.x - 1;
-                    }
+            } else {
+                // Moving towards the left.
+                if is_against_wall_left(&coords, anchored_y, context.tile_map) {
+                    steering.mode = SteeringMode::Falling {
+                        x_movement: Direction1D::Neutral,
+                        starting_y_pos,
+                        duration,
+                    };
+                } else if aligned_with_grid(steering.destination.x as f32, anchored_x, x_movement) {
+                    steering.destination.x = coords.pos.x - 1;
+                }
+            }
+        }
+        SteeringMode::Jumping {
+            x_movement,
+            starting_y_pos,
+            duration,
+        } => {
+            if !intent.jump_direction.is_neutral() {
+                steering.mode = SteeringMode::Jumping {
+                    x_movement: intent.jump_direction,
+                    starting_y_pos,
+                    duration,
+                };
+                steering.facing = Direction2D::from(intent.jump_direction, Direction1D::Neutral);
+            }
+            if x_movement.is_neutral() {
+                // No horizontal movement.
+                steering.destination.x = coords.pos.x;
+            } else if x_movement.is_positive() {
+                // Moving towards the right.
+                if aligned_with_grid(steering.destination.x as f32, anchored_x, x_movement)
+                    && !is_against_wall_right(&coords, coords.pos.y as f32, context.tile_map)
+                {
+                    steering.destination.x = coords.pos.x + 1;
+                }
+            } else {
+                // Moving towards the left.
+                if aligned_with_grid(steering.destination.x as f32, anchored_x, x_movement)
+                    && !is_against_wall_left(&coords, coords.pos.y as f32, context.tile_map)
+                {
+                    steering.destination.x = coords.pos.x - 1;
                 }
             }
-        };
-
-        // Push frame on history if player position changed.
-        if old_pos != coords.pos || history.force_key_frame {
-            history.push_frame(Frame::new(coords.pos));
         }
+    };
+
+    // Push frame on history if player position changed.
+    if old_pos != coords.pos || context.force_key_frame {
+        effects.push(SteeringEffect::PushFrame(coords.pos));
+    }
+    SteeringUpdate {
+        intent,
+        steering,
+        coords,
+        translation,
+        effects,
     }
 }
 
@@ -363,7 +499,7 @@ fn wrap(
     bounds: &WorldBounds,
     steering: &mut Steering,
     coords: &mut Coords,
-    transform: &mut Transform,
+    translation: &mut Vec2,
 ) {
     let delta = Pos::new(
         if coords.pos.x < bounds.x() && steering.facing.x == Direction1D::Negative {
@@ -392,6 +528,480 @@ fn wrap(
     coords.pos = coords.pos + delta;
     steering.destination = steering.destination + delta;
     steering.mode = steering.mode.wrap(delta.y as f32);
-    transform.translation.x += delta.x as f32;
-    transform.translation.y += delta.y as f32;
+    translation.x += delta.x as f32;
+    translation.y += delta.y as f32;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::levels::level_save::LevelSave;
+    use crate::levels::tiles::tile_defs::{CollisionDefinition, TileDefinitions};
+    use bevy::prelude::default;
+
+    use crate::systems::motion::structs::dimens::Dimens;
+
+    use super::*;
+
+    /// The character sizes that every rule is checked against.
+    const SIZES: [(i32, i32); 3] = [(1, 1), (1, 2), (3, 3)];
+
+    fn tile_map(blocks: &[Pos], ladders: &[Pos]) -> TileMap {
+        let mut tile_defs = TileDefinitions::default();
+        tile_defs.map.insert(
+            "Block".to_string(),
+            TileDefinition {
+                collision: Some(CollisionDefinition {
+                    collides_top: true,
+                    collides_side: true,
+                    collides_bottom: true,
+                }),
+                ..TileDefinition::fallback()
+            },
+        );
+        tile_defs.map.insert(
+            "Ladder".to_string(),
+            TileDefinition {
+                climbable: true,
+                collision: Some(CollisionDefinition {
+                    collides_top: true,
+                    collides_side: false,
+                    collides_bottom: false,
+                }),
+                ..TileDefinition::fallback()
+            },
+        );
+        let mut level = LevelSave::default();
+        for pos in blocks {
+            level.tiles.insert(*pos, "Block".to_string());
+        }
+        for pos in ladders {
+            level.tiles.insert(*pos, "Ladder".to_string());
+        }
+        TileMap::for_play(&level, tile_defs)
+    }
+
+    fn character(width: i32, height: i32) -> Coords {
+        Coords::new(Pos::new(0, 0), Dimens::new(width, height))
+    }
+
+    #[test]
+    fn grounded_if_any_column_has_floor() {
+        for (width, height) in SIZES {
+            let coords = character(width, height);
+            for x in 0..width {
+                let tile_map = tile_map(&[Pos::new(x, -1)], &[]);
+                assert!(is_grounded(&coords, &tile_map), "{width}x{height}, x {x}");
+            }
+            let beside = tile_map(&[Pos::new(-1, -1), Pos::new(width, -1)], &[]);
+            assert!(!is_grounded(&coords, &beside), "{width}x{height}");
+        }
+    }
+
+    #[test]
+    fn walls_block_along_full_height() {
+        for (width, height) in SIZES {
+            let coords = character(width, height);
+            for y in 0..height {
+                let right = tile_map(&[Pos::new(width, y)], &[]);
+                assert!(
+                    is_against_wall_right(&coords, 0., &right),
+                    "{width}x{height}"
+                );
+                assert!(
+                    !is_against_wall_left(&coords, 0., &right),
+                    "{width}x{height}"
+                );
+                let left = tile_map(&[Pos::new(-1, y)], &[]);
+                assert!(is_against_wall_left(&coords, 0., &left), "{width}x{height}");
+                assert!(
+                    !is_against_wall_right(&coords, 0., &left),
+                    "{width}x{height}"
+                );
+            }
+            let above_head = tile_map(&[Pos::new(width, height), Pos::new(-1, height)], &[]);
+            assert!(!is_against_wall_right(&coords, 0., &above_head));
+            assert!(!is_against_wall_left(&coords, 0., &above_head));
+        }
+    }
+
+    #[test]
+    fn ceiling_is_directly_above_head() {
+        for (width, height) in SIZES {
+            let coords = character(width, height);
+            for x in 0..width {
+                let ceiling = tile_map(&[Pos::new(x, height)], &[]);
+                assert!(is_underneath_ceiling(&coords, &ceiling), "{width}x{height}");
+            }
+            let higher = tile_map(&[Pos::new(0, height + 1)], &[]);
+            assert!(!is_underneath_ceiling(&coords, &higher), "{width}x{height}");
+        }
+    }
+
+    #[test]
+    fn climbing_needs_ladder_under_every_column() {
+        for (width, height) in SIZES {
+            let coords = character(width, height);
+            let full: Vec<Pos> = (0..width).map(|x| Pos::new(x, 0)).collect();
+            let ladder = tile_map(&[], &full);
+            assert!(can_climb_up(&coords, &ladder), "{width}x{height}");
+            let below: Vec<Pos> = (0..width).map(|x| Pos::new(x, -1)).collect();
+            let ladder_below = tile_map(&[], &below);
+            assert!(can_climb_down(&coords, &ladder_below), "{width}x{height}");
+            if width > 1 {
+                let partial = tile_map(&[], &full[1..]);
+                assert!(!can_climb_up(&coords, &partial), "{width}x{height}");
+            }
+        }
+    }
+
+    /// One frame of steering, starting from the given situation.
+    struct Case {
+        name: &'static str,
+        blocks: Vec<Pos>,
+        ladders: Vec<Pos>,
+        /// The discrete position the character had during the previous frame. The character is
+        /// placed exactly at `start`, which may differ from this if it moved or wrapped.
+        previous: Pos,
+        start: Pos,
+        steering: Steering,
+        intent: SteeringIntent,
+        max_safe_fall_height: Option<f32>,
+        expected_mode: &'static str,
+        expected_pos: Pos,
+        expected_destination: Option<Pos>,
+        /// The height the jump is measured from, if the character jumps.
+        expected_jump_start: Option<f32>,
+        expected_effects: Vec<SteeringEffect>,
+    }
+
+    impl Default for Case {
+        fn default() -> Self {
+            Case {
+                name: "",
+                blocks: vec![],
+                ladders: vec![],
+                previous: Pos::new(0, 0),
+                start: Pos::new(0, 0),
+                steering: Steering::new(Pos::new(0, 0)),
+                intent: SteeringIntent::default(),
+                max_safe_fall_height: None,
+                expected_mode: "Grounded",
+                expected_pos: Pos::new(0, 0),
+                expected_destination: None,
+                expected_jump_start: None,
+                expected_effects: vec![],
+            }
+        }
+    }
+
+    fn config(max_safe_fall_height: Option<f32>) -> MovementConfig {
+        MovementConfig {
+            player_speed: 10.,
+            enemy_speed: 8.,
+            jump_allowance: 0.1,
+            turn_allowance: 0.1,
+            jump_buffer: 0.1,
+            coyote_time: 0.08,
+            map_cursor_move_high_cooldown: 0.3,
+            map_cursor_move_low_cooldown: 0.05,
+            max_safe_fall_height,
+            jump: JumpArc::default(),
+        }
+    }
+
+    fn mode_name(mode: &SteeringMode) -> &'static str {
+        match mode {
+            SteeringMode::Grounded => "Grounded",
+            SteeringMode::Climbing => "Climbing",
+            SteeringMode::Falling { .. } => "Falling",
+            SteeringMode::Jumping { .. } => "Jumping",
+        }
+    }
+
+    fn with_mode(mode: SteeringMode) -> Steering {
+        Steering {
+            mode,
+            ..Steering::new(Pos::new(0, 0))
+        }
+    }
+
+    fn falling(starting_y_pos: f32, duration: f32) -> SteeringMode {
+        SteeringMode::Falling {
+            x_movement: Direction1D::Neutral,
+            starting_y_pos,
+            duration,
+        }
+    }
+
+    fn floor() -> Vec<Pos> {
+        (-1..3).map(|x| Pos::new(x, -1)).collect()
+    }
+
+    fn ladder(rows: std::ops::Range<i32>) -> Vec<Pos> {
+        rows.flat_map(|y| [Pos::new(0, y), Pos::new(1, y)])
+            .collect()
+    }
+
+    fn cases() -> Vec<Case> {
+        vec![
+            Case {
+                name: "climb down from the top of a ladder",
+                ladders: ladder(-3..0),
+                intent: SteeringIntent {
+                    climb: Direction1D::Negative,
+                    ..default()
+                },
+                expected_mode: "Climbing",
+                expected_destination: Some(Pos::new(0, -1)),
+                expected_effects: vec![SteeringEffect::Sound(SoundType::LadderStep)],
+                ..default()
+            },
+            Case {
+                name: "climb up to the top of a ladder",
+                ladders: ladder(-3..0),
+                steering: with_mode(SteeringMode::Climbing),
+                intent: SteeringIntent {
+                    climb: Direction1D::Positive,
+                    ..default()
+                },
+                ..default()
+            },
+            Case {
+                name: "climb up a ladder",
+                ladders: ladder(-3..3),
+                steering: with_mode(SteeringMode::Climbing),
+                intent: SteeringIntent {
+                    climb: Direction1D::Positive,
+                    ..default()
+                },
+                expected_mode: "Climbing",
+                expected_destination: Some(Pos::new(0, 1)),
+                expected_effects: vec![SteeringEffect::Sound(SoundType::LadderStep)],
+                ..default()
+            },
+            Case {
+                name: "land on the top of a ladder",
+                ladders: ladder(-3..0),
+                steering: with_mode(falling(3., 0.2)),
+                ..default()
+            },
+            Case {
+                name: "fall through the middle of a ladder",
+                ladders: ladder(-3..3),
+                steering: with_mode(falling(3., 0.2)),
+                expected_mode: "Falling",
+                ..default()
+            },
+            Case {
+                name: "land after a fall that is too high",
+                blocks: floor(),
+                steering: with_mode(falling(10., 0.6)),
+                max_safe_fall_height: Some(3.),
+                expected_effects: vec![SteeringEffect::FellToDeath],
+                ..default()
+            },
+            Case {
+                name: "land after a safe fall",
+                blocks: floor(),
+                steering: with_mode(falling(2., 0.1)),
+                max_safe_fall_height: Some(3.),
+                ..default()
+            },
+            Case {
+                name: "jump",
+                blocks: floor(),
+                intent: SteeringIntent {
+                    jump: true,
+                    ..default()
+                },
+                expected_mode: "Jumping",
+                expected_effects: vec![SteeringEffect::Sound(SoundType::Jump)],
+                ..default()
+            },
+            Case {
+                name: "jump underneath a ceiling",
+                blocks: [floor(), vec![Pos::new(1, 2)]].concat(),
+                intent: SteeringIntent {
+                    jump: true,
+                    ..default()
+                },
+                expected_effects: vec![SteeringEffect::Sound(SoundType::CannotPerformAction)],
+                ..default()
+            },
+            Case {
+                name: "jump underneath a high ceiling",
+                blocks: [floor(), vec![Pos::new(1, 3)]].concat(),
+                intent: SteeringIntent {
+                    jump: true,
+                    ..default()
+                },
+                expected_mode: "Jumping",
+                expected_effects: vec![SteeringEffect::Sound(SoundType::Jump)],
+                ..default()
+            },
+            Case {
+                name: "climb up underneath a ceiling",
+                ladders: ladder(-3..2),
+                blocks: vec![Pos::new(0, 2)],
+                steering: with_mode(SteeringMode::Climbing),
+                intent: SteeringIntent {
+                    climb: Direction1D::Positive,
+                    ..default()
+                },
+                ..default()
+            },
+            Case {
+                name: "walk off a ledge",
+                expected_mode: "Falling",
+                ..default()
+            },
+            Case {
+                name: "jump just after walking off a ledge",
+                steering: Steering {
+                    walked_off_ledge: true,
+                    ..with_mode(falling(1., 0.02))
+                },
+                intent: SteeringIntent {
+                    jump: true,
+                    ..default()
+                },
+                expected_mode: "Jumping",
+                expected_effects: vec![SteeringEffect::Sound(SoundType::Jump)],
+                ..default()
+            },
+            Case {
+                name: "jump after falling a tile from a ledge",
+                start: Pos::new(0, -1),
+                steering: Steering {
+                    walked_off_ledge: true,
+                    ..with_mode(falling(1., 0.07))
+                },
+                intent: SteeringIntent {
+                    jump: true,
+                    ..default()
+                },
+                expected_mode: "Jumping",
+                expected_jump_start: Some(1.),
+                expected_effects: vec![SteeringEffect::Sound(SoundType::Jump)],
+                ..default()
+            },
+            Case {
+                name: "jump while falling after a jump",
+                steering: with_mode(falling(0., 0.02)),
+                intent: SteeringIntent {
+                    jump: true,
+                    ..default()
+                },
+                expected_mode: "Falling",
+                ..default()
+            },
+            Case {
+                name: "take a step",
+                blocks: floor(),
+                intent: SteeringIntent {
+                    walk: Direction1D::Positive,
+                    ..default()
+                },
+                expected_destination: Some(Pos::new(1, 0)),
+                expected_effects: vec![SteeringEffect::Sound(SoundType::Step)],
+                ..default()
+            },
+            Case {
+                name: "walk into a wall",
+                blocks: [floor(), vec![Pos::new(2, 1)]].concat(),
+                intent: SteeringIntent {
+                    walk: Direction1D::Positive,
+                    ..default()
+                },
+                expected_destination: Some(Pos::new(0, 0)),
+                ..default()
+            },
+            Case {
+                name: "wrap around the left edge of the world",
+                blocks: vec![Pos::new(15, -1), Pos::new(-16, -1)],
+                previous: Pos::new(-16, 0),
+                start: Pos::new(-17, 0),
+                steering: Steering {
+                    facing: Direction2D::new(-1., 0.),
+                    ..Steering::new(Pos::new(-17, 0))
+                },
+                expected_pos: Pos::new(15, 0),
+                expected_effects: vec![SteeringEffect::PushFrame(Pos::new(15, 0))],
+                ..default()
+            },
+            Case {
+                name: "wrap around the bottom edge of the world",
+                previous: Pos::new(0, -12),
+                start: Pos::new(0, -13),
+                steering: with_mode(falling(0., 0.9)),
+                expected_mode: "Falling",
+                expected_pos: Pos::new(0, 11),
+                expected_effects: vec![SteeringEffect::PushFrame(Pos::new(0, 11))],
+                ..default()
+            },
+            Case {
+                name: "climb down through the bottom edge of the world",
+                ladders: [ladder(-12..-10), ladder(10..12)].concat(),
+                previous: Pos::new(0, -12),
+                start: Pos::new(0, -13),
+                steering: Steering {
+                    facing: Direction2D::new(0., -1.),
+                    ..with_mode(SteeringMode::Climbing)
+                },
+                expected_mode: "Climbing",
+                expected_pos: Pos::new(0, 11),
+                expected_effects: vec![SteeringEffect::PushFrame(Pos::new(0, 11))],
+                ..default()
+            },
+        ]
+    }
+
+    #[test]
+    fn steering_table() {
+        for case in cases() {
+            let tile_map = tile_map(&case.blocks, &case.ladders);
+            let config = config(case.max_safe_fall_height);
+            let context = SteeringContext {
+                tile_map: &tile_map,
+                config: &config,
+                arc: &config.jump,
+                delta_seconds: 0.01,
+                force_key_frame: false,
+            };
+            let coords = Coords::new(case.previous, Dimens::new(2, 2));
+            let (x, y) = coords.to_centered_coords(case.start);
+            let update = steer(
+                &case.intent,
+                &case.steering,
+                &coords,
+                Vec2::new(x, y),
+                &context,
+            );
+            let name = case.name;
+            assert_eq!(
+                mode_name(&update.steering.mode),
+                case.expected_mode,
+                "{name}"
+            );
+            assert_eq!(update.coords.pos, case.expected_pos, "{name}");
+            if let Some(destination) = case.expected_destination {
+                assert_eq!(update.steering.destination, destination, "{name}");
+            }
+            if let Some(expected) = case.expected_jump_start {
+                match update.steering.mode {
+                    SteeringMode::Jumping { starting_y_pos, .. } => {
+                        assert!((starting_y_pos - expected).abs() < f32::EPSILON, "{name}");
+                    }
+                    _ => panic!("{name}: not jumping"),
+                }
+            }
+            assert_eq!(update.effects, case.expected_effects, "{name}");
+            let expected_translation = update.coords.to_centered_coords(case.expected_pos);
+            assert!(
+                (update.translation.x - expected_translation.0).abs() < f32::EPSILON
+                    && (update.translation.y - expected_translation.1).abs() < f32::EPSILON,
+                "{name}"
+            );
+        }
+    }
 }
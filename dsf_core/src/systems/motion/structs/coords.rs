@@ -38,6 +38,18 @@ impl Coords {
         self.overlaps(&other)
     }
 
+    /// True iff an entity with these coords touches the given item, such as a key or a tool. Both
+    /// are positioned by their translation, the center point of the entity, so that items can also
+    /// be picked up while moving between two tiles.
+    ///
+    /// The entity must reach into the inner two thirds of the item; merely brushing against its
+    /// edge is not enough.
+    pub fn touches_item(&self, translation: Vec3, item: &Coords, item_translation: Vec3) -> bool {
+        let reach = self.dimens.as_vec2() / 2. + item.dimens.as_vec2() / 3.;
+        let distance = (translation - item_translation).truncate().abs();
+        distance.x < reach.x && distance.y < reach.y
+    }
+
     /// Converts the given discrete position to a translation, taking into account the dimensions
     /// of the entity.
     ///
@@ -61,3 +73,41 @@ impl Coords {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Places a character of the given size on the floor at x = 0 and a 2x2 item on the same floor
+    /// at the given x. Returns whether the character touches the item.
+    fn touches_item_at(width: i32, height: i32, item_x: f32) -> bool {
+        let character = Coords::new(Pos::new(0, 0), Dimens::new(width, height));
+        let item = Coords::new(Pos::new(0, 0), Dimens::new(2, 2));
+        let (x, y) = character.to_centered_coords(character.pos);
+        let (_, item_y) = item.to_centered_coords(item.pos);
+        character.touches_item(
+            Vec3::new(x, y, 0.),
+            &item,
+            Vec3::new(item_x + 1., item_y, 0.),
+        )
+    }
+
+    #[test]
+    fn touches_items_from_dimens() {
+        for (width, height) in [(1, 1), (1, 2), (3, 3)] {
+            // Item directly next to the character: not touching yet.
+            assert!(
+                !touches_item_at(width, height, width as f32),
+                "{width}x{height}"
+            );
+            assert!(!touches_item_at(width, height, -2.), "{width}x{height}");
+            // Reaching into the inner two thirds of the item.
+            assert!(
+                touches_item_at(width, height, width as f32 - 0.5),
+                "{width}x{height}"
+            );
+            assert!(touches_item_at(width, height, -1.5), "{width}x{height}");
+            assert!(touches_item_at(width, height, 0.), "{width}x{height}");
+        }
+    }
+}
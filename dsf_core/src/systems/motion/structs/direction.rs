@@ -3,6 +3,8 @@ use std::ops::Not;
 use bevy::prelude::Vec2;
 use serde::{Deserialize, Serialize};
 
+use crate::systems::motion::structs::pos::Pos;
+
 #[derive(Deserialize, Serialize, Default, Copy, Clone, Debug, PartialEq)]
 pub struct Direction2D {
     pub x: Direction1D,
@@ -119,3 +121,39 @@ impl Not for Direction1D {
         }
     }
 }
+
+/// One of the four directions along the axes of the grid.
+#[derive(Deserialize, Serialize, Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CardinalDirection {
+    Up,
+    #[default]
+    Down,
+    Left,
+    Right,
+}
+
+impl CardinalDirection {
+    /// The offset of a single step in this direction.
+    #[must_use]
+    pub fn offset(&self) -> Pos {
+        match self {
+            CardinalDirection::Up => Pos::new(0, 1),
+            CardinalDirection::Down => Pos::new(0, -1),
+            CardinalDirection::Left => Pos::new(-1, 0),
+            CardinalDirection::Right => Pos::new(1, 0),
+        }
+    }
+}
+
+impl Not for CardinalDirection {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        match self {
+            CardinalDirection::Up => CardinalDirection::Down,
+            CardinalDirection::Down => CardinalDirection::Up,
+            CardinalDirection::Left => CardinalDirection::Right,
+            CardinalDirection::Right => CardinalDirection::Left,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Describes how characters jump and fall. The default is configured in the `MovementConfig`.
+/// A character that should jump differently can be given its own `JumpArc` component, which then
+/// takes precedence over the config.
+///
+/// A jump is a parabola that goes up to the given height and peaks at the given time. After the
+/// peak, the character falls down at a constant speed.
+#[derive(Component, Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JumpArc {
+    /// How many tiles high the character gets at the peak of its jump.
+    pub height: f32,
+    /// The time in seconds between starting the jump and reaching its peak.
+    pub peak_time: f32,
+    /// How many tiles per second a character drops while falling. Falling blocks use this too.
+    pub fall_speed: f32,
+}
+
+impl Default for JumpArc {
+    fn default() -> Self {
+        JumpArc {
+            height: 2.2,
+            peak_time: 0.209,
+            fall_speed: 15.,
+        }
+    }
+}
+
+impl JumpArc {
+    /// The y offset from the starting position, the given number of seconds after starting the
+    /// jump.
+    #[must_use]
+    pub fn jump_delta_y(&self, duration: f32) -> f32 {
+        -self.gravity() * (duration - self.peak_time).powf(2.) + self.height
+    }
+
+    /// The y offset from the starting position, the given number of seconds after starting to fall.
+    #[must_use]
+    pub fn fall_delta_y(&self, duration: f32) -> f32 {
+        duration * -self.fall_speed
+    }
+
+    /// The steepness of the parabola, derived from the height and the peak time so that the jump
+    /// starts exactly at the starting position.
+    fn gravity(&self) -> f32 {
+        self.height / self.peak_time.powf(2.)
+    }
+}
@@ -1,6 +1,7 @@
 pub mod coords;
 pub mod dimens;
 pub mod direction;
+pub mod jump_arc;
 pub mod player;
 pub mod pos;
 pub mod steering;
@@ -1,13 +1,9 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::levels::tiles::tile_defs::ToolType;
-
 /// The entity with this component is the player.
 #[derive(Component, Deserialize, Serialize, Default, Copy, Clone, Debug)]
 pub struct Player {
-    /// The tool currently equipped by the player.
-    pub equipped: Option<ToolType>,
     /// Whether the jump key is currently down. Needed to figure out if the player wants to jump
     /// this frame. (Jump is only executed if this value changes from false to true.)
     pub pressing_jump: bool,
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::systems::motion::structs::direction::Direction2D;
+use crate::systems::motion::structs::jump_arc::JumpArc;
 use crate::systems::motion::structs::pos::Pos;
 use crate::systems::motion::structs::steering_mode::SteeringMode;
 
@@ -16,6 +17,12 @@ pub struct Steering {
     pub facing: Direction2D,
     pub destination: Pos,
     pub mode: SteeringMode,
+    /// Seconds since the entity wanted to jump, for as long as that jump has not been performed.
+    /// See `MovementConfig::jump_buffer`.
+    pub jump_buffer: Option<f32>,
+    /// True while the entity is falling because it walked off a ledge. For a short while after
+    /// that, it can still jump. See `MovementConfig::coyote_time`.
+    pub walked_off_ledge: bool,
 }
 
 impl Steering {
@@ -24,6 +31,8 @@ impl Steering {
             facing: Direction2D::new(1., 0.),
             destination: pos,
             mode: SteeringMode::Grounded,
+            jump_buffer: None,
+            walked_off_ledge: false,
         }
     }
 
@@ -42,9 +51,18 @@ impl Steering {
         matches!(self.mode, SteeringMode::Jumping { .. })
     }
 
-    pub fn jump_has_peaked(&self) -> bool {
+    pub fn jump_has_peaked(&self, arc: &JumpArc) -> bool {
         if let SteeringMode::Jumping { duration, .. } = self.mode {
-            duration > 0.209
+            duration > arc.peak_time
+        } else {
+            false
+        }
+    }
+
+    /// True if the entity walked off a ledge no more than the given number of seconds ago.
+    pub fn within_coyote_time(&self, coyote_time: f32) -> bool {
+        if let SteeringMode::Falling { duration, .. } = self.mode {
+            self.walked_off_ledge && duration <= coyote_time
         } else {
             false
         }
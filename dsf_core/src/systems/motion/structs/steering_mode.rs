@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::systems::motion::structs::direction::Direction1D;
+use crate::systems::motion::structs::jump_arc::JumpArc;
 
 /// SteeringMode influences max speeds, ability to jump, ability to move, etc.
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -45,15 +46,15 @@ impl SteeringMode {
     /// Calculate the y offset from the initial y-position at the time this movement began.
     /// This method is only valid for SteeringMode::Falling and SteeringMode::Jumping. It will
     /// return 0. otherwise.
-    pub fn calc_delta_y(&self, duration: f32) -> f32 {
+    pub fn calc_delta_y(&self, duration: f32, arc: &JumpArc) -> f32 {
         match self {
-            SteeringMode::Jumping { .. } => -50. * (duration - 0.209).powf(2.) + 2.2,
-            SteeringMode::Falling { .. } => duration * -15.,
+            SteeringMode::Jumping { .. } => arc.jump_delta_y(duration),
+            SteeringMode::Falling { .. } => arc.fall_delta_y(duration),
             _ => 0.,
         }
     }
 
-    pub fn jump_to_fall(&self) -> Self {
+    pub fn jump_to_fall(&self, arc: &JumpArc) -> Self {
         if let SteeringMode::Jumping {
             x_movement,
             starting_y_pos,
@@ -62,8 +63,8 @@ impl SteeringMode {
         {
             SteeringMode::Falling {
                 x_movement,
-                starting_y_pos: starting_y_pos + self.calc_delta_y(0.209),
-                duration: duration - 0.209,
+                starting_y_pos: starting_y_pos + self.calc_delta_y(arc.peak_time, arc),
+                duration: duration - arc.peak_time,
             }
         } else {
             panic!("Not allowed.");
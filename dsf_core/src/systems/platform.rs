@@ -0,0 +1,290 @@
+use bevy::prelude::*;
+
+use crate::levels::tiles::tile_defs::PlatformDefinition;
+use crate::levels::tiles::tilemap::TileMap;
+use crate::levels::world_bounds::WorldBounds;
+use crate::systems::death::death_anim::Dying;
+use crate::systems::enemy::spawner::Enemy;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::player::Player;
+use crate::systems::motion::structs::pos::Pos;
+use crate::systems::motion::structs::steering::Steering;
+use crate::systems::signals::{SignalWire, Signals};
+
+/// Both the player and enemies can ride platforms.
+type CharacterFilter = (Or<(With<Player>, With<Enemy>)>, Without<Dying>);
+
+type PlatformComponents<'a> = (
+    &'a mut Platform,
+    &'a mut Coords,
+    &'a mut Transform,
+    &'a Waypoints,
+    Option<&'a SignalWire>,
+);
+
+/// The waypoints a platform travels along, as configured in the level's paths.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Waypoints(pub Vec<Pos>);
+
+/// A solid tile that moves across the grid one tile at a time.
+///
+/// While it moves, the platform is kept up to date in the `TileMap`, so characters can stand on it
+/// and bump into it just like any other tile.
+#[derive(Component, Debug)]
+pub struct Platform {
+    pub definition: PlatformDefinition,
+    /// Where the platform started out. The route starts and ends here.
+    origin: Pos,
+    /// The position of the platform along its route. Unlike the platform's Coords, this is not
+    /// wrapped, so a route can cross the edge of the world.
+    track_pos: Pos,
+    /// Index of the waypoint the platform is heading towards. Zero means the origin.
+    target: usize,
+    /// Counts down to the next step.
+    timer: Timer,
+    /// Tiles without collision that the platform is passing over, by anchor position. The
+    /// `TileMap` holds a single tile per cell, so these are taken out of it while the platform
+    /// covers them, and put back once it has moved on.
+    covered: Vec<(Pos, String)>,
+}
+
+impl Platform {
+    #[must_use]
+    pub fn new(definition: PlatformDefinition, pos: Pos) -> Self {
+        let timer = Timer::from_seconds(definition.pause, false);
+        Platform {
+            definition,
+            origin: pos,
+            track_pos: pos,
+            target: 1,
+            timer,
+            covered: Vec::new(),
+        }
+    }
+
+    /// Returns the waypoints of a route that starts at the given position, which is where the
+    /// platform currently is, and then follows the rest of the platform's route. This is used to
+    /// save a platform halfway along its route.
+    #[must_use]
+    pub fn remaining_route(&self, waypoints: &Waypoints, pos: Pos) -> Vec<Pos> {
+        let offset = pos - self.track_pos;
+        let stops = std::iter::once(self.origin)
+            .chain(waypoints.0.iter().copied())
+            .collect::<Vec<Pos>>();
+        let mut route = (0..stops.len())
+            .map(|i| stops[(self.target + i) % stops.len()] + offset)
+            .collect::<Vec<Pos>>();
+        if route.last() == Some(&pos) {
+            route.pop();
+        }
+        route
+    }
+
+    /// Returns the position the platform is currently heading towards.
+    fn target_pos(&self, waypoints: &Waypoints) -> Pos {
+        if self.target == 0 {
+            self.origin
+        } else {
+            waypoints
+                .0
+                .get(self.target - 1)
+                .copied()
+                .unwrap_or(self.origin)
+        }
+    }
+
+    /// Returns the offset of a single step towards the current target. The platform moves
+    /// horizontally first, then vertically.
+    fn next_step(&self, waypoints: &Waypoints) -> Pos {
+        let delta = self.target_pos(waypoints) - self.track_pos;
+        if delta.x != 0 {
+            Pos::new(delta.x.signum(), 0)
+        } else {
+            Pos::new(0, delta.y.signum())
+        }
+    }
+
+    /// Moves on to the next waypoint, returning to the origin after the last one.
+    fn select_next_target(&mut self, waypoints: &Waypoints) {
+        self.target = (self.target + 1) % (waypoints.0.len() + 1);
+        self.timer = Timer::from_seconds(self.step_duration() + self.definition.pause, false);
+    }
+
+    fn step_duration(&self) -> f32 {
+        1. / self.definition.speed.max(f32::EPSILON)
+    }
+}
+
+/// Moves platforms along their paths, one tile at a time.
+///
+/// Characters standing on top of a platform are carried along. A platform waits if a tile that
+/// collides in its direction of travel or a character (other than its riders) is in its way, or if
+/// it would push its riders into a ceiling. Tiles without such collision are passed over.
+/// Platforms that are wired to a signal only move while that signal is on.
+pub fn move_platforms(
+    time: Res<Time>,
+    signals: Res<Signals>,
+    mut tile_map: ResMut<TileMap>,
+    mut query_platforms: Query<PlatformComponents, Without<Steering>>,
+    mut query_characters: Query<
+        (Entity, &mut Coords, &mut Transform, &mut Steering),
+        CharacterFilter,
+    >,
+) {
+    for (mut platform, mut coords, mut transform, waypoints, wire) in query_platforms.iter_mut() {
+        if wire.is_some_and(|wire| !signals.is_active(&wire.signal)) {
+            continue;
+        }
+        platform.timer.tick(time.delta());
+        if !platform.timer.finished() {
+            continue;
+        }
+        let step = platform.next_step(waypoints);
+        if step == Pos::default() {
+            platform.select_next_target(waypoints);
+            continue;
+        }
+        let bounds = tile_map.world_bounds;
+        let old_cells = cells(&coords, &bounds);
+        let new_coords = Coords::new(bounds.wrapped(&(coords.pos + step)), coords.dimens);
+        let leading_cells = cells(&new_coords, &bounds)
+            .into_iter()
+            .filter(|pos| !old_cells.contains(pos))
+            .collect::<Vec<Pos>>();
+        if leading_cells
+            .iter()
+            .any(|pos| is_obstacle(pos, step, &tile_map))
+        {
+            continue;
+        }
+
+        // Hazardous platforms, such as crushers, do not stop for characters that are in the way.
+        let crushes = tile_map
+            .get_tile(&coords.pos)
+            .is_some_and(|tile| tile.hazard.is_some());
+        let mut riders = Vec::new();
+        let mut blocked = false;
+        for (entity, character, _, steering) in query_characters.iter() {
+            if steering.is_grounded() && stands_on(character, &old_cells, &bounds) {
+                blocked |= step.y > 0 && hits_ceiling(character, &tile_map);
+                riders.push(entity);
+            } else if cells(character, &bounds)
+                .iter()
+                .any(|pos| leading_cells.contains(pos))
+            {
+                blocked |= !crushes;
+            }
+        }
+        if blocked {
+            continue;
+        }
+
+        let key = tile_map.get_tile_def_key(&coords.pos).cloned();
+        if key.is_some() {
+            tile_map.remove_tile(&coords.pos);
+        }
+        for pos in &leading_cells {
+            if let Some(anchor) = tile_map.get_actual_pos(pos) {
+                if let Some(covered) = tile_map.get_tile_def_key(&anchor).cloned() {
+                    tile_map.remove_tile(&anchor);
+                    platform.covered.push((anchor, covered));
+                }
+            }
+        }
+        let new_cells = cells(&new_coords, &bounds);
+        let (covered, uncovered): (Vec<_>, Vec<_>) = std::mem::take(&mut platform.covered)
+            .into_iter()
+            .partition(|(anchor, key)| {
+                let dimens = tile_map.tile_defs.get(key).dimens;
+                cells(&Coords::new(*anchor, dimens), &bounds)
+                    .iter()
+                    .any(|pos| new_cells.contains(pos))
+            });
+        platform.covered = covered;
+        for (anchor, key) in uncovered {
+            let dimens = tile_map.tile_defs.get(&key).dimens;
+            tile_map.put_tile(&anchor, dimens, key);
+        }
+        if let Some(key) = key {
+            tile_map.put_tile(&new_coords.pos, coords.dimens, key);
+        }
+        *coords = new_coords;
+        let (centered_x, centered_y) = coords.to_centered_coords(coords.pos);
+        transform.translation.x = centered_x;
+        transform.translation.y = centered_y;
+        platform.track_pos = platform.track_pos + step;
+        platform.timer = Timer::from_seconds(platform.step_duration(), false);
+        if platform.track_pos == platform.target_pos(waypoints) {
+            platform.select_next_target(waypoints);
+        }
+
+        for rider in riders {
+            if let Ok((_, mut character, mut transform, mut steering)) =
+                query_characters.get_mut(rider)
+            {
+                if step.x != 0 && is_against_wall(&character, step.x, &tile_map) {
+                    // The rider stays behind and the platform slides out from under them.
+                    continue;
+                }
+                shift(&mut character, &mut transform, &mut steering, step);
+                let wrap_delta = bounds.wrapped(&character.pos) - character.pos;
+                shift(&mut character, &mut transform, &mut steering, wrap_delta);
+            }
+        }
+    }
+}
+
+/// Returns all positions covered by the given coords, wrapped around the world bounds.
+fn cells(coords: &Coords, bounds: &WorldBounds) -> Vec<Pos> {
+    (0..coords.dimens.x)
+        .flat_map(|x| (0..coords.dimens.y).map(move |y| (x, y)))
+        .map(|(x, y)| bounds.wrapped(&coords.pos.append_xy(x, y)))
+        .collect()
+}
+
+/// True iff a character with the given coords is standing on any of the given positions.
+fn stands_on(character: &Coords, platform_cells: &[Pos], bounds: &WorldBounds) -> bool {
+    (0..character.dimens.x)
+        .map(|x| bounds.wrapped(&character.pos.append_xy(x, -1)))
+        .any(|pos| platform_cells.contains(&pos))
+}
+
+/// True iff the character cannot be lifted up by one tile.
+fn hits_ceiling(character: &Coords, tile_map: &TileMap) -> bool {
+    (0..character.dimens.x).any(|x| {
+        tile_map
+            .get_tile(&character.pos.append_xy(x, character.dimens.y))
+            .is_some_and(|tile| tile.collides_bottom() || tile.collides_horizontally())
+    })
+}
+
+/// True iff there is a tile at the given position that a platform moving by the given step would
+/// bump into. Tiles without collision in that direction, like keys and tools, are passed through.
+fn is_obstacle(pos: &Pos, step: Pos, tile_map: &TileMap) -> bool {
+    tile_map.get_tile(pos).is_some_and(|tile| {
+        (step.x != 0 && tile.collides_horizontally())
+            || (step.y > 0 && tile.collides_bottom())
+            || (step.y < 0 && tile.provides_platform())
+    })
+}
+
+/// True iff the character cannot be moved one tile in the given horizontal direction.
+fn is_against_wall(character: &Coords, direction: i32, tile_map: &TileMap) -> bool {
+    let x = if direction > 0 {
+        character.dimens.x
+    } else {
+        -1
+    };
+    (0..character.dimens.y).any(|y| {
+        tile_map
+            .get_tile(&character.pos.append_xy(x, y))
+            .is_some_and(|tile| tile.collides_horizontally())
+    })
+}
+
+fn shift(coords: &mut Coords, transform: &mut Transform, steering: &mut Steering, delta: Pos) {
+    coords.pos = coords.pos + delta;
+    steering.destination = steering.destination + delta;
+    transform.translation.x += delta.x as f32;
+    transform.translation.y += delta.y as f32;
+}
@@ -1,12 +1,11 @@
 use bevy::prelude::*;
 
 use crate::audio::sound_event::SoundEvent;
+use crate::levels::tiles::tile_defs::RevolvingDoorDefinition;
 use crate::levels::tiles::tilemap::TileMap;
 use crate::loading::assets::SoundType;
 use crate::systems::motion::structs::coords::Coords;
-use crate::systems::motion::structs::dimens::Dimens;
 use crate::systems::motion::structs::direction::Direction1D;
-use crate::systems::motion::structs::player::Player;
 use crate::systems::motion::structs::steering::Steering;
 use crate::systems::motion::structs::steering_intent::SteeringIntent;
 
@@ -16,15 +15,18 @@ const TIME_ANIMATION: f32 = 0.5;
 #[derive(Component)]
 pub struct RevolvingDoor {
     pub state: RevolvingState,
-    /// The direction the player would be facing if they used the door.
+    /// The direction a character would be facing if they used the door.
     pub facing: Direction1D,
+    /// The tile definitions this door switches between.
+    pub definition: RevolvingDoorDefinition,
 }
 
 impl RevolvingDoor {
-    pub fn new(direction: Direction1D) -> Self {
+    pub fn new(definition: RevolvingDoorDefinition) -> Self {
         RevolvingDoor {
             state: RevolvingState::Idle,
-            facing: direction,
+            facing: definition.facing,
+            definition,
         }
     }
 }
@@ -40,94 +42,129 @@ pub struct RevolvingController {
     pub state: ControllerState,
     /// How many door fragments in this door.
     pub size: usize,
-    /// The direction the player would be facing if they used the door.
+    /// The direction a character would be facing if they used the door.
     pub facing: Direction1D,
 }
 
+/// Tracks which character is currently using the door.
 #[derive(Debug)]
 pub enum ControllerState {
     Idle,
-    Activation(Timer),
-    Animation(Timer),
+    Activation(Timer, Entity),
+    Animation(Timer, Entity),
 }
 
+/// Both the player and enemies can use revolving doors. A character standing next to a door and
+/// walking into it in the direction the door allows will activate it. After a short while, the
+/// door turns and the character is forced to walk through it to the other side.
 pub fn control_revolving_doors(
     mut tile_map: ResMut<TileMap>,
     mut audio: EventWriter<SoundEvent>,
     time: Res<Time>,
     mut query_ctrl: Query<(&mut RevolvingController, &Coords)>,
     mut query_door: Query<(&mut RevolvingDoor, &Coords)>,
-    mut query_player: Query<(&mut SteeringIntent, &Steering, &Coords), With<Player>>,
+    mut query_characters: Query<(Entity, &mut SteeringIntent, &Steering, &Coords)>,
 ) {
-    if let Ok((mut intent, steering, coords)) = query_player.get_single_mut() {
-        for (mut ctrl, ctrl_coords) in query_ctrl.iter_mut() {
-            let player_in_position = coords.pos.x
-                == ctrl_coords.pos.x + ctrl.facing.signum_i() * -2
-                && coords.pos.y <= ctrl_coords.pos.y
-                && coords.pos.y > ctrl_coords.pos.y - ctrl.size as i32;
-            let player_walking_at_door = steering.is_grounded() && intent.walk == ctrl.facing;
-            match &mut ctrl.state {
-                ControllerState::Idle => {
-                    if player_in_position && player_walking_at_door {
-                        ctrl.state = ControllerState::Activation(Timer::from_seconds(
-                            TIME_ACTIVATION,
-                            false,
-                        ));
-                    }
-                }
-                ControllerState::Activation(timer) => {
-                    timer.tick(time.delta());
-                    if timer.finished() {
-                        ctrl.state =
-                            ControllerState::Animation(Timer::from_seconds(TIME_ANIMATION, false));
-                        audio.send(SoundEvent::Sfx(SoundType::SpawnerOpenClose, false));
-                        intent.forced_walk = Some(coords.pos.append_x(ctrl.facing.signum_i() * 4));
-                        for (mut door, child_block) in
-                            query_door.iter_mut().filter(|(_, child_block)| {
-                                child_block.pos.x == ctrl_coords.pos.x
-                                    && child_block.pos.y <= ctrl_coords.pos.y
-                                    && child_block.pos.y > ctrl_coords.pos.y - ctrl.size as i32
-                            })
-                        {
-                            door.state = RevolvingState::TurningTowards;
-                            tile_map.put_tile(
-                                &child_block.pos,
-                                Dimens::new(2, 1),
-                                "RevolvingDoorNonColliding".to_string(),
-                            );
+    for (mut ctrl, ctrl_coords) in query_ctrl.iter_mut() {
+        let (facing, size) = (ctrl.facing, ctrl.size);
+        let wants_to_pass = |steering: &Steering, intent: &SteeringIntent, coords: &Coords| {
+            in_position(facing, size, ctrl_coords, coords)
+                && steering.is_grounded()
+                && intent.walk == facing
+        };
+        let next_state = match &mut ctrl.state {
+            ControllerState::Idle => query_characters
+                .iter()
+                .find(|(_, intent, steering, coords)| wants_to_pass(steering, intent, coords))
+                .map(|(character, _, _, _)| {
+                    ControllerState::Activation(
+                        Timer::from_seconds(TIME_ACTIVATION, false),
+                        character,
+                    )
+                }),
+            ControllerState::Activation(timer, character) => {
+                timer.tick(time.delta());
+                let character = *character;
+                match query_characters.get_mut(character) {
+                    Ok((_, mut intent, steering, coords))
+                        if wants_to_pass(steering, &intent, coords) =>
+                    {
+                        if timer.finished() {
+                            audio.send(SoundEvent::Sfx(SoundType::SpawnerOpenClose, false));
+                            let distance = coords.dimens.x + ctrl_coords.dimens.x;
+                            intent.forced_walk =
+                                Some(coords.pos.append_x(facing.signum_i() * distance));
+                            for (mut door, door_coords) in
+                                query_door.iter_mut().filter(|(_, door_coords)| {
+                                    is_fragment(size, ctrl_coords, door_coords)
+                                })
+                            {
+                                door.state = RevolvingState::TurningTowards;
+                                tile_map.put_tile(
+                                    &door_coords.pos,
+                                    door_coords.dimens,
+                                    door.definition.turning.clone(),
+                                );
+                            }
+                            Some(ControllerState::Animation(
+                                Timer::from_seconds(TIME_ANIMATION, false),
+                                character,
+                            ))
+                        } else {
+                            None
                         }
-                    } else if !player_in_position || !player_walking_at_door {
-                        ctrl.state = ControllerState::Idle;
                     }
+                    _ => Some(ControllerState::Idle),
                 }
-                ControllerState::Animation(timer) => {
-                    timer.tick(time.delta());
-                    if timer.finished() {
-                        ctrl.state = ControllerState::Idle;
-                        ctrl.facing = !ctrl.facing;
+            }
+            ControllerState::Animation(timer, character) => {
+                timer.tick(time.delta());
+                if timer.finished() {
+                    if let Ok((_, mut intent, _, _)) = query_characters.get_mut(*character) {
                         intent.forced_walk = None;
-                        for (mut door, child_coords) in
-                            query_door.iter_mut().filter(|(_, child_coords)| {
-                                child_coords.pos.x == ctrl_coords.pos.x
-                                    && child_coords.pos.y <= ctrl_coords.pos.y
-                                    && child_coords.pos.y > ctrl_coords.pos.y - ctrl.size as i32
-                            })
-                        {
-                            door.state = RevolvingState::Idle;
-                            door.facing = ctrl.facing;
-                            tile_map.put_tile(
-                                &child_coords.pos,
-                                Dimens::new(2, 1),
-                                "RevolvingDoorFacingPositive".to_string(),
-                            );
-                        }
                     }
+                    for (mut door, door_coords) in query_door
+                        .iter_mut()
+                        .filter(|(_, door_coords)| is_fragment(size, ctrl_coords, door_coords))
+                    {
+                        door.state = RevolvingState::Idle;
+                        door.facing = !facing;
+                        let key = door.definition.key_for(door.facing).to_string();
+                        tile_map.put_tile(&door_coords.pos, door_coords.dimens, key);
+                    }
+                    ctrl.facing = !facing;
+                    Some(ControllerState::Idle)
+                } else {
+                    None
                 }
             }
+        };
+        if let Some(next_state) = next_state {
+            ctrl.state = next_state;
         }
     }
 }
 
+/// True if the character is standing right next to the door, on the side it can be entered from.
+fn in_position(facing: Direction1D, size: usize, ctrl_coords: &Coords, coords: &Coords) -> bool {
+    let x_in_position = if facing.is_negative() {
+        coords.pos.x == ctrl_coords.pos.x + ctrl_coords.dimens.x
+    } else {
+        coords.pos.x + coords.dimens.x == ctrl_coords.pos.x
+    };
+    x_in_position && covers_y(size, ctrl_coords, coords.pos.y)
+}
+
+/// True if the door block is one of the fragments in the column headed by the controller.
+fn is_fragment(size: usize, ctrl_coords: &Coords, door_coords: &Coords) -> bool {
+    door_coords.pos.x == ctrl_coords.pos.x && covers_y(size, ctrl_coords, door_coords.pos.y)
+}
+
+/// True if the given height falls within the column of door fragments headed by the controller.
+fn covers_y(size: usize, ctrl_coords: &Coords, y: i32) -> bool {
+    y <= ctrl_coords.pos.y && y > ctrl_coords.pos.y - size as i32
+}
+
 pub fn control_revolving_sprites(mut query: Query<(&RevolvingDoor, &mut TextureAtlasSprite)>) {
     for (door, mut sprite) in query.iter_mut() {
         sprite.flip_x = door.facing.is_negative();
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::audio::sound_event::SoundEvent;
+use crate::levels::tiles::objects::Collectible;
+use crate::loading::assets::SoundType;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::player::Player;
+
+/// Keeps track of how well the player is doing in the current attempt at a level.
+/// This is reset each time the level is (re)loaded.
+#[derive(Debug, Default)]
+pub struct LevelStats {
+    /// The total value of all collectibles picked up so far.
+    pub score: u32,
+    /// How many seconds the player has spent on this attempt.
+    pub time: f32,
+    /// How many times the player died in this level before the current attempt.
+    pub deaths: usize,
+}
+
+impl LevelStats {
+    #[must_use]
+    pub fn new(deaths: usize) -> Self {
+        LevelStats {
+            deaths,
+            ..default()
+        }
+    }
+}
+
+/// The player picks up any collectible they touch, adding its value to the score.
+pub fn collect_treasure(
+    mut commands: Commands,
+    mut audio: EventWriter<SoundEvent>,
+    mut stats: ResMut<LevelStats>,
+    query_player: Query<&Coords, With<Player>>,
+    query_collectibles: Query<(Entity, &Coords, &Collectible)>,
+) {
+    for player in query_player.iter() {
+        for (entity, _, collectible) in query_collectibles
+            .iter()
+            .filter(|(_, coords, _)| coords.overlaps(player))
+        {
+            audio.send(SoundEvent::Sfx(SoundType::PickupKey, false));
+            stats.score += collectible.score;
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn tick_level_time(time: Res<Time>, mut stats: ResMut<LevelStats>) {
+    stats.time += time.delta_seconds();
+}
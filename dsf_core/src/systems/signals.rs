@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+
+use crate::levels::tiles::tile_defs::{MechanismDefinition, TriggerType};
+use crate::levels::tiles::tilemap::TileMap;
+use crate::loading::assets::AssetStorage;
+use crate::loading::entities::inflate::spawn_from_def;
+use crate::systems::enemy::spawner::Enemy;
+use crate::systems::motion::structs::coords::Coords;
+use crate::systems::motion::structs::player::Player;
+use crate::systems::win_checking::WinCondition;
+
+/// Attached to every tile that the level's wiring connects to a signal.
+/// For triggers, this is the signal they emit. For everything else, this is the signal they
+/// listen to.
+#[derive(Component, Debug, Clone)]
+pub struct SignalWire {
+    pub signal: String,
+}
+
+impl SignalWire {
+    #[must_use]
+    pub fn new(signal: String) -> Self {
+        SignalWire { signal }
+    }
+}
+
+/// Sent whenever a signal is switched on or off. Receivers (mechanisms, spawners, traps) listen
+/// for these.
+#[derive(Debug, Clone)]
+pub struct SignalChanged {
+    pub signal: String,
+    pub active: bool,
+}
+
+/// Keeps track of which signals are currently switched on.
+/// A signal is on if at least one of the triggers wired to it is active.
+#[derive(Debug, Default)]
+pub struct Signals {
+    active: HashSet<String>,
+    /// On the first update after loading a level, every signal is broadcast once, so that all
+    /// receivers start out in the correct state.
+    initialised: bool,
+}
+
+impl Signals {
+    #[must_use]
+    pub fn is_active(&self, signal: &str) -> bool {
+        self.active.contains(signal)
+    }
+}
+
+/// A tile that emits a signal.
+#[derive(Component, Debug)]
+pub struct Trigger {
+    pub trigger_type: TriggerType,
+    /// Whether this trigger is currently emitting its signal.
+    pub active: bool,
+    /// Whether the player was standing on this trigger during the previous update.
+    /// Used to flip levers only once each time the player steps onto them.
+    occupied: bool,
+}
+
+impl Trigger {
+    #[must_use]
+    pub fn new(trigger_type: TriggerType) -> Self {
+        Trigger {
+            trigger_type,
+            active: false,
+            occupied: false,
+        }
+    }
+}
+
+/// A tile that switches to its counterpart whenever the signal it is wired to changes.
+#[derive(Component, Debug)]
+pub struct Mechanism {
+    pub definition: MechanismDefinition,
+}
+
+impl Mechanism {
+    #[must_use]
+    pub fn new(definition: MechanismDefinition) -> Self {
+        Mechanism { definition }
+    }
+}
+
+/// Updates the state of all triggers, then broadcasts any signals that changed as a result.
+pub fn update_triggers(
+    win: Res<WinCondition>,
+    mut signals: ResMut<Signals>,
+    mut events: EventWriter<SignalChanged>,
+    mut query_triggers: Query<(
+        &mut Trigger,
+        &Coords,
+        &SignalWire,
+        Option<&mut TextureAtlasSprite>,
+    )>,
+    query_player: Query<&Coords, With<Player>>,
+    query_enemies: Query<&Coords, With<Enemy>>,
+) {
+    let mut states: HashMap<String, bool> = HashMap::new();
+    for (mut trigger, coords, wire, sprite) in query_triggers.iter_mut() {
+        let player_on_it = query_player.iter().any(|player| player.overlaps(coords));
+        trigger.active = match trigger.trigger_type {
+            TriggerType::Lever => {
+                let flip = player_on_it && !trigger.occupied;
+                trigger.active ^ flip
+            }
+            TriggerType::PressurePlate => {
+                player_on_it || query_enemies.iter().any(|enemy| enemy.overlaps(coords))
+            }
+            TriggerType::KeysCollected(amount) => win.nr_keys_collected() >= amount,
+        };
+        trigger.occupied = player_on_it;
+        if let Some(mut sprite) = sprite {
+            sprite.flip_x = trigger.active;
+        }
+        *states.entry(wire.signal.clone()).or_default() |= trigger.active;
+    }
+    let broadcast_all = !signals.initialised;
+    signals.initialised = true;
+    for (signal, active) in states {
+        if broadcast_all || signals.is_active(&signal) != active {
+            if active {
+                signals.active.insert(signal.clone());
+            } else {
+                signals.active.remove(&signal);
+            }
+            events.send(SignalChanged { signal, active });
+        }
+    }
+}
+
+/// Swaps mechanisms for their counterparts when their signal changes. This updates both the
+/// entity and the tile map.
+pub fn switch_mechanisms(
+    mut commands: Commands,
+    storage: Res<AssetStorage>,
+    mut tile_map: ResMut<TileMap>,
+    mut events: EventReader<SignalChanged>,
+    query: Query<(Entity, &Mechanism, &Coords, &SignalWire)>,
+) {
+    for event in events.iter() {
+        for (entity, mechanism, coords, wire) in query.iter().filter(|(_, mechanism, _, wire)| {
+            wire.signal == event.signal && mechanism.definition.powered != event.active
+        }) {
+            let counterpart = &mechanism.definition.counterpart;
+            commands.entity(entity).despawn_recursive();
+            let new_entity = spawn_from_def(
+                &mut commands,
+                &storage,
+                coords.pos,
+                counterpart,
+                tile_map.tile_defs.get(counterpart),
+            );
+            commands.entity(new_entity).insert(wire.clone());
+            let dimens = tile_map.tile_defs.get(counterpart).dimens;
+            tile_map.remove_tile(&coords.pos);
+            tile_map.put_tile(&coords.pos, dimens, counterpart.clone());
+        }
+    }
+}
@@ -1,75 +1,78 @@
+use std::collections::HashSet;
+
 use bevy::prelude::*;
 
 use crate::audio::sound_event::SoundEvent;
-use crate::levels::tiles::objects::Tool;
-use crate::levels::tiles::tile_defs::{TileDefinition, ToolType};
+use crate::config::settings::difficulty_settings::DifficultySettings;
+use crate::levels::tiles::objects::{LevelTile, Tool};
+use crate::levels::tiles::tile_defs::{PlacementDefinition, TileDefinition, ToolType};
 use crate::levels::tiles::tilemap::TileMap;
 use crate::levels::world_bounds::WorldBounds;
 use crate::loading::assets::{AssetStorage, SoundType};
+use crate::loading::entities::inflate::spawn_from_def;
+use crate::systems::inventory::{CarriedTool, Dropped, Inventory};
 use crate::systems::motion::structs::coords::Coords;
-use crate::systems::motion::structs::player::{EquippedTag, Player};
+use crate::systems::motion::structs::dimens::Dimens;
+use crate::systems::motion::structs::player::Player;
 use crate::systems::motion::structs::pos::Pos;
 use crate::systems::motion::structs::steering::Steering;
 
-/// Tool width and height, hardcoded for now.
-/// TODO: Don't hardcode.
-const TOOL_WIDTH: f32 = 2.;
-const TOOL_HEIGHT: f32 = 2.;
+type ToolComponents<'a> = (
+    &'a Tool,
+    &'a LevelTile,
+    &'a Coords,
+    &'a Transform,
+    Entity,
+    Option<&'a Dropped>,
+);
+
+/// Added to tiles that were placed by a tool with a limited lifetime. Once the timer runs out, the
+/// tile disappears again.
+#[derive(Component, Debug, Clone)]
+pub struct Temporary {
+    pub timer: Timer,
+}
 
 /// Checks if the player intersects any tools.
-/// If so, the tool will equipped by the player and will be removed from the game.
+/// If so, and there is room in the player's inventory, the tool will be equipped by the player and
+/// will be removed from the game.
 pub fn pickup_system(
     mut audio: EventWriter<SoundEvent>,
     mut commands: Commands,
-    storage: Res<AssetStorage>,
-    mut query_player: Query<(&mut Player, Entity, &Coords, &Transform)>,
-    query_tools: Query<(&Tool, &Transform, Entity)>,
+    settings: Res<DifficultySettings>,
+    mut query_player: Query<(&mut Inventory, &Coords, &Transform), With<Player>>,
+    query_tools: Query<ToolComponents>,
 ) {
-    let player = query_player
-        .iter_mut()
-        .map(|(player, entity, coords, transform)| {
-            (
-                player,
-                entity,
-                Vec2::new(transform.translation.x, transform.translation.y),
-                Vec2::new(coords.dimens.x as f32, coords.dimens.y as f32),
-            )
-        })
-        .next();
-    if let Some((mut player, player_entity, pos, dimens)) = player {
-        if player.equipped.is_some() {
+    if let Some((mut inventory, player, player_transform)) = query_player.iter_mut().next() {
+        let touches = |coords: &Coords, transform: &Transform| {
+            player.touches_item(player_transform.translation, coords, transform.translation)
+        };
+        // Tools that were just dropped can be picked up again once the player stepped away.
+        for (_, _, _, _, entity, _) in
+            query_tools
+                .iter()
+                .filter(|(_, _, coords, transform, _, dropped)| {
+                    dropped.is_some() && !touches(coords, transform)
+                })
+        {
+            commands.entity(entity).remove::<Dropped>();
+        }
+        if !inventory.has_room(settings.inventory_slots) {
             return;
         }
         // Find the first tool that intersects with the player:
-        let tool_opt = query_tools.iter().find(|(_, transform, _)| {
-            let key_x = transform.translation.x;
-            let key_y = transform.translation.y;
-            pos.x - dimens.x / 2. < key_x + TOOL_WIDTH / 3.
-                && pos.x + dimens.x / 2. > key_x - TOOL_WIDTH / 3.
-                && pos.y - dimens.y / 2. < key_y + TOOL_HEIGHT / 3.
-                && pos.y + dimens.y / 2. > key_y - TOOL_HEIGHT / 3.
-        });
-        if let Some((tool, _, tool_entity)) = tool_opt {
+        let tool_opt = query_tools
+            .iter()
+            .find(|(_, _, coords, transform, _, dropped)| {
+                dropped.is_none() && touches(coords, transform)
+            });
+        if let Some((tool, level_tile, _, _, tool_entity, _)) = tool_opt {
             audio.send(SoundEvent::Sfx(SoundType::PickupTool, false));
-            player.equipped = Some(tool.tool_type);
+            inventory.add(CarriedTool {
+                key: level_tile.0.clone(),
+                tool: tool.clone(),
+            });
             commands.entity(tool_entity).despawn_recursive();
-
-            let atlas = storage.get_atlas(&tool.sprite);
-            let equipped = commands
-                .spawn()
-                .insert(EquippedTag)
-                .insert_bundle(SpriteSheetBundle {
-                    texture_atlas: atlas,
-                    transform: Transform::from_xyz(0., 0., 0.),
-                    sprite: TextureAtlasSprite {
-                        index: tool.sprite_nr,
-                        custom_size: Some(Vec2::new(TOOL_WIDTH, TOOL_HEIGHT)),
-                        ..default()
-                    },
-                    ..default()
-                })
-                .id();
-            commands.entity(player_entity).push_children(&[equipped]);
         }
     }
 }
@@ -77,21 +80,58 @@ pub fn pickup_system(
 pub fn use_tool_system(
     mut commands: Commands,
     mut audio: EventWriter<SoundEvent>,
-    mut query_player: Query<(&mut Player, &Steering, &Coords)>,
-    query_tags: Query<(Entity, &EquippedTag)>,
-    query_blocks: Query<(Entity, &Coords)>,
+    mut query_player: Query<(&mut Inventory, &Steering, &Coords, &Transform)>,
+    query_blocks: Query<(Entity, &Coords, Option<&Steering>)>,
     mut tile_map: ResMut<TileMap>,
+    storage: Res<AssetStorage>,
     keys: Res<Input<KeyCode>>,
 ) {
-    let wants_to_use_tool = keys.just_pressed(KeyCode::Space);
+    let wants_to_use_tool = keys.just_pressed(KeyCode::Q);
     if !wants_to_use_tool {
         return;
     }
-    for (mut player, steering, player_coords) in query_player.iter_mut() {
+    for (mut inventory, steering, player_coords, player_transform) in query_player.iter_mut() {
         if !steering.is_grounded() {
             return;
         }
-        let targeted_blocks = match player.equipped {
+        let tool_type = inventory.equipped_type();
+        if let Some((placement, anchors)) = tool_type
+            .as_ref()
+            .and_then(|tool_type| placement_targets(tool_type, steering, player_coords, &tile_map))
+        {
+            let tile_def = tile_map.tile_defs.get(&placement.tile).clone();
+            let characters = query_blocks
+                .iter()
+                .filter(|(_, _, character)| character.is_some())
+                .map(|(_, coords, _)| coords);
+            if can_place(&anchors, &tile_def, &tile_map, characters) {
+                audio.send(SoundEvent::Sfx(SoundType::TrapWallCreated, false));
+                if let Some(broken) = inventory.wear_equipped() {
+                    break_tool(
+                        &mut commands,
+                        &mut audio,
+                        &storage,
+                        &broken,
+                        tile_map.tile_defs.get(&broken.key).dimens,
+                        player_transform,
+                    );
+                }
+                for pos in anchors {
+                    tile_map.put_tile(&pos, tile_def.dimens, placement.tile.clone());
+                    let entity =
+                        spawn_from_def(&mut commands, &storage, pos, &placement.tile, &tile_def);
+                    if let Some(lifetime) = placement.lifetime {
+                        commands.entity(entity).insert(Temporary {
+                            timer: Timer::from_seconds(lifetime, false),
+                        });
+                    }
+                }
+            } else {
+                audio.send(SoundEvent::Sfx(SoundType::CannotPerformAction, false));
+            }
+            continue;
+        }
+        let targeted_blocks = match tool_type {
             Some(ToolType::BreakBlocksHorizontally(depth)) => {
                 let player_is_not_too_far_away_from_wall = at_least_one_is_breakable(
                     &tiles_to_side(1, steering, player_coords, &tile_map.world_bounds),
@@ -121,14 +161,20 @@ pub fn use_tool_system(
             let none_are_unbreakable = none_are_unbreakable(&targeted_blocks, &tile_map);
             if at_least_one_is_breakable && none_are_unbreakable {
                 audio.send(SoundEvent::Sfx(SoundType::Mining, false));
-                player.equipped = None;
+                if let Some(broken) = inventory.wear_equipped() {
+                    break_tool(
+                        &mut commands,
+                        &mut audio,
+                        &storage,
+                        &broken,
+                        tile_map.tile_defs.get(&broken.key).dimens,
+                        player_transform,
+                    );
+                }
                 for pos in &targeted_blocks {
                     tile_map.remove_tile(pos);
                 }
-                for (entity, _) in query_tags.iter() {
-                    commands.entity(entity).despawn_recursive();
-                }
-                for (entity, block_coords) in query_blocks.iter() {
+                for (entity, block_coords, _) in query_blocks.iter() {
                     if targeted_blocks.contains(&block_coords.pos) {
                         commands.entity(entity).despawn_recursive();
                     }
@@ -138,6 +184,136 @@ pub fn use_tool_system(
     }
 }
 
+/// How long it takes for a broken tool to fade away.
+const BREAK_ANIM_SECS: f32 = 0.8;
+
+/// The remains of a tool that just broke. It tumbles upward and fades out.
+#[derive(Component, Debug, Clone)]
+pub struct BrokenTool {
+    pub timer: Timer,
+}
+
+/// Plays the sound and spawns the animation for a tool that just broke in the player's hands.
+fn break_tool(
+    commands: &mut Commands,
+    audio: &mut EventWriter<SoundEvent>,
+    storage: &AssetStorage,
+    broken: &CarriedTool,
+    dimens: Dimens,
+    player_transform: &Transform,
+) {
+    audio.send(SoundEvent::Sfx(SoundType::ToolBreak, false));
+    commands
+        .spawn_bundle(SpriteSheetBundle {
+            texture_atlas: storage.get_atlas(&broken.tool.sprite),
+            transform: Transform::from_translation(player_transform.translation),
+            sprite: TextureAtlasSprite {
+                index: broken.tool.sprite_nr,
+                custom_size: Some(dimens.as_vec2()),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(BrokenTool {
+            timer: Timer::from_seconds(BREAK_ANIM_SECS, false),
+        });
+}
+
+/// Broken tools tumble upward while fading out, then disappear.
+pub fn animate_broken_tools(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut BrokenTool,
+        &mut Transform,
+        &mut TextureAtlasSprite,
+    )>,
+) {
+    for (entity, mut broken, mut transform, mut sprite) in query.iter_mut() {
+        broken.timer.tick(time.delta());
+        if broken.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            let delta = time.delta_seconds();
+            transform.translation.y += 3. * delta;
+            transform.rotate_z(-6. * delta);
+            sprite.color.set_a(broken.timer.percent_left());
+        }
+    }
+}
+
+/// Tiles placed by a tool with a limited lifetime disappear from both the world and the tile map
+/// once their time runs out.
+pub fn expire_temporary_tiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tile_map: ResMut<TileMap>,
+    mut query: Query<(Entity, &mut Temporary, &Coords)>,
+) {
+    for (entity, mut temporary, coords) in query.iter_mut() {
+        temporary.timer.tick(time.delta());
+        if temporary.timer.finished() {
+            tile_map.remove_tile(&coords.pos);
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// For tools that add terrain, returns what they place and the positions of the tiles that would be
+/// placed.
+fn placement_targets<'a>(
+    tool_type: &'a ToolType,
+    steering: &Steering,
+    coords: &Coords,
+    tile_map: &TileMap,
+) -> Option<(&'a PlacementDefinition, Vec<Pos>)> {
+    let bounds = &tile_map.world_bounds;
+    match tool_type {
+        ToolType::PlaceLadder(placement) => {
+            let height = tile_map.tile_defs.get(&placement.tile).dimens.y;
+            let anchors = (0..i32::from(placement.depth))
+                .map(|y| bounds.wrapped(&coords.pos.append_y(y * height)))
+                .collect();
+            Some((placement, anchors))
+        }
+        ToolType::PlaceBridge(placement) => {
+            let floor = Coords::new(coords.pos.append_y(-1), Dimens::new(coords.dimens.x, 1));
+            let anchors = tiles_to_side(placement.depth, steering, &floor, bounds);
+            Some((placement, anchors))
+        }
+        ToolType::PlaceBlocks(placement) => {
+            let anchors = tiles_to_side(placement.depth, steering, coords, bounds);
+            Some((placement, anchors))
+        }
+        _ => None,
+    }
+}
+
+/// Tiles can only be placed if all positions they would cover are empty. Tiles that block movement
+/// also cannot be placed on top of characters.
+fn can_place<'a>(
+    anchors: &[Pos],
+    tile_def: &TileDefinition,
+    tile_map: &TileMap,
+    mut characters: impl Iterator<Item = &'a Coords>,
+) -> bool {
+    let covered: Vec<Pos> = anchors
+        .iter()
+        .flat_map(|anchor| {
+            (0..tile_def.dimens.x)
+                .flat_map(move |x| (0..tile_def.dimens.y).map(move |y| anchor.append_xy(x, y)))
+        })
+        .map(|pos| tile_map.world_bounds.wrapped(&pos))
+        .collect();
+    let no_overlap = covered.iter().collect::<HashSet<_>>().len() == covered.len();
+    let all_empty = covered.iter().all(|pos| tile_map.get_tile(pos).is_none());
+    let blocks_characters = tile_def.collides_horizontally() || tile_def.collides_bottom();
+    let no_characters_inside = !blocks_characters
+        || !characters.any(|character| covered.iter().any(|pos| character.overlaps_pos(pos)));
+    no_overlap && all_empty && no_characters_inside
+}
+
 fn at_least_one_is_breakable(blocks: &[Pos], tile_map: &TileMap) -> bool {
     blocks.iter().any(|pos| {
         tile_map
@@ -1,54 +1,121 @@
 use bevy::prelude::*;
 
 use crate::audio::sound_event::SoundEvent;
+use crate::levels::tiles::tile_defs::TrapDefinition;
 use crate::levels::tiles::tilemap::TileMap;
 use crate::levels::world_bounds::WorldBounds;
 use crate::loading::assets::{AssetStorage, SoundType};
 use crate::loading::entities::inflate::spawn_from_def;
+use crate::systems::enemy::spawner::Enemy;
 use crate::systems::motion::structs::coords::Coords;
-use crate::systems::motion::structs::dimens::Dimens;
 use crate::systems::motion::structs::player::Player;
 use crate::systems::motion::structs::pos::Pos;
+use crate::systems::signals::{SignalChanged, SignalWire};
 
-const COOLDOWN: f32 = 1.;
+/// Both the player and enemies can set off traps.
+type CharacterFilter = Or<(With<Player>, With<Enemy>)>;
 
 #[derive(Component, Default)]
 pub struct TrappedWall {
     /// Countdown to trigger the trap.
     pub timer: Option<Timer>,
+    /// What this segment turns into, how fast and in which direction the trap grows.
+    pub definition: TrapDefinition,
 }
 
+impl TrappedWall {
+    #[must_use]
+    pub fn new(definition: TrapDefinition) -> Self {
+        TrappedWall {
+            timer: None,
+            definition,
+        }
+    }
+
+    pub fn start_timer(&mut self) {
+        self.timer = Some(Timer::from_seconds(self.definition.delay, false));
+    }
+}
+
+/// Any character (the player or an enemy) that steps into a trapped tile sets off the trap that
+/// tile belongs to.
 pub fn trigger_trap_walls(
     tile_map: Res<TileMap>,
-    query_player: Query<&Coords, With<Player>>,
+    query_characters: Query<&Coords, CharacterFilter>,
+    mut query_wall: Query<(&mut TrappedWall, &Coords)>,
+) {
+    let mut vec: Vec<Pos> = query_characters
+        .iter()
+        .flat_map(|coords| tiles_inside(coords, &tile_map.world_bounds))
+        .filter_map(|pos| {
+            tile_map
+                .get_tile(&pos)
+                .and_then(|def| def.trap())
+                .map(|trap| first_segment(&tile_map, &pos, trap))
+        })
+        .collect();
+    vec.sort();
+    vec.dedup();
+    for (mut trap, _) in query_wall
+        .iter_mut()
+        .filter(|(trap, wall_coords)| vec.contains(&wall_coords.pos) && trap.timer.is_none())
+    {
+        trap.start_timer();
+    }
+}
+
+/// A trap that is wired to a signal fires as soon as that signal switches on, regardless of
+/// whether anyone is standing in it.
+pub fn fire_wired_traps(
+    tile_map: Res<TileMap>,
+    mut events: EventReader<SignalChanged>,
+    query_wired: Query<(&Coords, &SignalWire), With<TrappedWall>>,
     mut query_wall: Query<(&mut TrappedWall, &Coords)>,
 ) {
-    if let Ok(player_coords) = query_player.get_single() {
-        let mut vec: Vec<Pos> = tiles_inside(player_coords, &tile_map.world_bounds)
-            .iter()
-            .filter(|pos| tile_map.get_tile(pos).map_or(false, |def| def.is_trapped()))
-            .map(|trap_pos| {
-                let mut offset = 0;
-                loop {
-                    offset += 1;
-                    let up_is_also_trapped = tile_map
-                        .get_tile(&trap_pos.append_y(offset))
-                        .map_or(false, |def| def.is_trapped());
-                    if !up_is_also_trapped {
-                        return trap_pos.append_y(offset - 1);
-                    }
-                }
-            })
-            .collect();
-        vec.sort();
-        vec.dedup();
-        for (mut trap, _) in query_wall
-            .iter_mut()
-            .filter(|(trap, wall_coords)| vec.contains(&wall_coords.pos) && trap.timer.is_none())
-        {
-            trap.timer = Some(Timer::from_seconds(COOLDOWN, false));
+    let starts: Vec<Pos> = events
+        .iter()
+        .filter(|event| event.active)
+        .flat_map(|event| {
+            query_wired
+                .iter()
+                .filter(move |(_, wire)| wire.signal == event.signal)
+        })
+        .filter_map(|(coords, _)| {
+            tile_map
+                .get_tile(&coords.pos)
+                .and_then(|def| def.trap())
+                .map(|trap| first_segment(&tile_map, &coords.pos, trap))
+        })
+        .collect();
+    for (mut trap, _) in query_wall
+        .iter_mut()
+        .filter(|(trap, wall_coords)| starts.contains(&wall_coords.pos) && trap.timer.is_none())
+    {
+        trap.start_timer();
+    }
+}
+
+/// Walks from the given trapped tile against the trap's growth direction, to find the segment
+/// where the trap starts.
+fn first_segment(tile_map: &TileMap, trap_pos: &Pos, trap: &TrapDefinition) -> Pos {
+    let step = (!trap.direction).offset();
+    // The trap might wrap all the way around the world, so make sure this terminates.
+    let max_steps = tile_map
+        .world_bounds
+        .width()
+        .max(tile_map.world_bounds.height());
+    let mut pos = tile_map.world_bounds.wrapped(trap_pos);
+    for _ in 0..max_steps {
+        let previous = tile_map.world_bounds.wrapped(&(pos + step));
+        let previous_is_also_trapped = tile_map
+            .get_tile(&previous)
+            .is_some_and(|def| def.is_trapped());
+        if !previous_is_also_trapped {
+            break;
         }
+        pos = previous;
     }
+    pos
 }
 
 // TODO: This was copied almost verbatim from tools. Get rid of duplicate code.
@@ -61,6 +128,10 @@ fn tiles_inside(coords: &Coords, bounds: &WorldBounds) -> Vec<Pos> {
         .collect()
 }
 
+/// Turns triggered trap segments into their result tiles once their timers run out.
+///
+/// A segment will not close while the player is standing in it. An enemy caught in a closing
+/// segment is crushed.
 pub fn trap_mechanism(
     mut commands: Commands,
     time: Res<Time>,
@@ -68,45 +139,50 @@ pub fn trap_mechanism(
     mut tile_map: ResMut<TileMap>,
     mut audio: EventWriter<SoundEvent>,
     mut query_trap: Query<(Entity, &mut TrappedWall, &Coords)>,
-    query_player: Query<&Coords, With<Player>>,
+    query_characters: Query<(Entity, &Coords, Option<&Enemy>), CharacterFilter>,
 ) {
-    if let Ok(player) = query_player.get_single() {
-        let next = query_trap
-            .iter_mut()
-            .filter_map(|(entity, mut trap, coords)| {
-                if let Some(timer) = &mut trap.timer {
-                    timer.tick(time.delta());
-                    if timer.finished() && !player.overlaps_pos(&coords.pos) {
-                        audio.send(SoundEvent::Sfx(SoundType::TrapWallCreated, false));
-                        commands.entity(entity).despawn_recursive();
-                        spawn_from_def(
-                            &mut commands,
-                            &storage,
-                            coords.pos,
-                            tile_map.tile_defs.get("Block2"),
-                        );
-                        tile_map.put_tile(&coords.pos, Dimens::new(1, 1), "Block2".to_string());
-                        let there_is_another_one = tile_map
-                            .get_tile(&coords.pos.append_y(-1))
-                            .map_or(false, |def| def.is_trapped());
-                        if there_is_another_one {
-                            Some(coords.pos.append_y(-1))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<Pos>>();
-
-        for (_, mut trap, coords) in query_trap.iter_mut() {
-            if next.contains(&coords.pos) {
-                trap.timer = Some(Timer::from_seconds(COOLDOWN, false));
+    let next = query_trap
+        .iter_mut()
+        .filter_map(|(entity, mut trap, coords)| {
+            let timer = trap.timer.as_mut()?;
+            timer.tick(time.delta());
+            let caught = query_characters
+                .iter()
+                .filter(|(_, character, _)| character.overlaps_pos(&coords.pos))
+                .map(|(character, _, enemy)| (character, enemy.is_some()))
+                .collect::<Vec<_>>();
+            let player_in_the_way = caught.iter().any(|(_, is_enemy)| !is_enemy);
+            if !timer.finished() || player_in_the_way {
+                return None;
             }
+            for (enemy, _) in caught {
+                commands.entity(enemy).despawn_recursive();
+            }
+            audio.send(SoundEvent::Sfx(SoundType::TrapWallCreated, false));
+            commands.entity(entity).despawn_recursive();
+            let result = &trap.definition.result;
+            spawn_from_def(
+                &mut commands,
+                &storage,
+                coords.pos,
+                result,
+                tile_map.tile_defs.get(result),
+            );
+            let dimens = tile_map.tile_defs.get(result).dimens;
+            tile_map.put_tile(&coords.pos, dimens, result.clone());
+            let next_pos = tile_map
+                .world_bounds
+                .wrapped(&(coords.pos + trap.definition.direction.offset()));
+            let there_is_another_one = tile_map
+                .get_tile(&next_pos)
+                .is_some_and(|def| def.is_trapped());
+            there_is_another_one.then_some(next_pos)
+        })
+        .collect::<Vec<Pos>>();
+
+    for (_, mut trap, coords) in query_trap.iter_mut() {
+        if next.contains(&coords.pos) && trap.timer.is_none() {
+            trap.start_timer();
         }
     }
 }
@@ -11,11 +11,6 @@ use crate::systems::motion::structs::pos::Pos;
 use crate::systems::motion::structs::steering::Steering;
 use crate::systems::win_handling::WinResource;
 
-/// Key width and height, hardcoded for now.
-/// TODO: Get rid of these hardcoded constants.
-const KEY_WIDTH: f32 = 2.;
-const KEY_HEIGHT: f32 = 2.;
-
 /// Maintains some information related to winning the level.
 /// In any given level, the player must collect all keys. Once all keys are collected, the exit door
 /// opens. When the player then reaches the door, they complete the level.
@@ -24,17 +19,25 @@ pub struct WinCondition {
     /// The set of positions of keys that are left in the level. If this collection is empty, then
     /// the player has collected all keys and is free to finish the level by reaching the exit door.
     pub keys: HashSet<Pos>,
+    /// How many keys the player has collected so far.
+    collected: usize,
 }
 
 impl WinCondition {
     /// Reset when (re)loading a level.
     pub fn reset(&mut self) {
         self.keys.clear();
+        self.collected = 0;
     }
     /// Add a key. Only to be used when loading a level.
     pub fn add_key(&mut self, pos: Pos) {
         self.keys.insert(pos);
     }
+    /// Sets how many keys the player has collected so far. Only to be used when restoring a
+    /// checkpoint, after the remaining keys have been added.
+    pub fn restore_keys_collected(&mut self, collected: usize) {
+        self.collected = collected;
+    }
     /// How many keys are left uncollected in the level.
     #[must_use]
     pub fn nr_keys_left(&self) -> usize {
@@ -42,7 +45,14 @@ impl WinCondition {
     }
     /// Sets the key at the given position as collected.
     pub fn set_key_collected(&mut self, pos: Pos) {
-        self.keys.remove(&pos);
+        if self.keys.remove(&pos) {
+            self.collected += 1;
+        }
+    }
+    /// How many keys the player has collected so far in the level.
+    #[must_use]
+    pub fn nr_keys_collected(&self) -> usize {
+        self.collected
     }
     /// Whether or not the player has collected all keys.
     /// If this returns true, the door is open and once the player reaches it they win the level.
@@ -62,25 +72,11 @@ pub fn key_collect_system(
     query_keys: Query<(&Coords, &Transform, Entity), With<Key>>,
     query_key_displays: Query<(&KeyDisplay, Entity)>,
 ) {
-    let player_collider = query_player
-        .iter()
-        .map(|(_, coords, transform)| {
-            (
-                Vec2::new(transform.translation.x, transform.translation.y),
-                Vec2::new(coords.dimens.x as f32, coords.dimens.y as f32),
-            )
-        })
-        .next();
-    if let Some((pos, dimens)) = player_collider {
+    if let Some((_, player, player_transform)) = query_player.iter().next() {
         let collected_key = query_keys
             .iter()
-            .filter(|(_, transform, _)| {
-                let key_x = transform.translation.x;
-                let key_y = transform.translation.y;
-                pos.x - dimens.x / 2. < key_x + KEY_WIDTH / 3.
-                    && pos.x + dimens.x / 2. > key_x - KEY_WIDTH / 3.
-                    && pos.y - dimens.y / 2. < key_y + KEY_HEIGHT / 3.
-                    && pos.y + dimens.y / 2. > key_y - KEY_HEIGHT / 3.
+            .filter(|(key, transform, _)| {
+                player.touches_item(player_transform.translation, key, transform.translation)
             })
             .map(|(key, _, entity)| (key, entity))
             .next();
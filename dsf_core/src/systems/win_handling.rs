@@ -10,8 +10,10 @@ use crate::levels::tiles::objects::ExitDoor;
 use crate::loading::assets::SoundType;
 use crate::states::AppState;
 use crate::systems::animations::structs::AnimationTimer;
+use crate::systems::checkpoint::CheckpointSave;
 use crate::systems::motion::structs::coords::Coords;
 use crate::systems::motion::structs::player::Player;
+use crate::systems::scoring::LevelStats;
 use crate::util::files::get_levels_dir;
 
 const TIME_DOOR_ANIM: f32 = 1.;
@@ -43,6 +45,7 @@ pub fn has_won(win: Option<Res<WinResource>>) -> bool {
 pub fn handle_win_queued(
     mut instructions: ResMut<LevelSelectionInstruction>,
     mut progression: ResMut<Progression>,
+    stats: Res<LevelStats>,
     mut audio: EventWriter<SoundEvent>,
     mut win: ResMut<WinResource>,
     mut query_player: Query<
@@ -53,6 +56,14 @@ pub fn handle_win_queued(
 ) {
     if let WinState::Queued = win.state {
         audio.send(SoundEvent::Sfx(SoundType::Win, true));
+        let level_name = instructions
+            .level
+            .as_ref()
+            .and_then(|level| level.file_name())
+            .map(|name| name.to_string_lossy().to_string());
+        if let Some(level_name) = level_name {
+            progression.record_result(&level_name, stats.score, stats.time, stats.deaths);
+        }
         progression.increment();
         instructions.level =
             Some(get_levels_dir().join(progression.levels.get(progression.current_level).unwrap()));
@@ -114,6 +125,14 @@ pub fn handle_win_player(
     }
 }
 
-pub fn clean_resources(mut commands: Commands) {
+pub fn clean_resources(
+    mut commands: Commands,
+    mut checkpoint: ResMut<CheckpointSave>,
+    win: Option<Res<WinResource>>,
+) {
+    if win.is_some() {
+        // The level was completed, next time it is played it starts from the beginning.
+        checkpoint.clear();
+    }
     commands.remove_resource::<WinResource>();
 }
@@ -1,58 +1,63 @@
 use bevy::prelude::*;
-use bevy::window::WindowMode;
+use bevy::window::{WindowMode, WindowResized};
+
+use crate::config::launch_options::LaunchOptions;
+use crate::config::settings::display_settings::DisplaySettings;
+
+/// The size of a windowed window is only saved once the user has stopped resizing it for this
+/// many seconds, so dragging the edge of the window does not write the settings every frame.
+const RESIZE_SAVE_DELAY: f32 = 0.5;
+
+/// A window size that has not been saved yet.
+#[derive(Default)]
+pub struct PendingResize {
+    size: Option<(u32, u32)>,
+    seconds_passed: f32,
+}
 
 /// Handle some general behaviour related to the window that should be executed in any State.
-pub fn handle_window(mut keys: ResMut<Input<KeyCode>>, mut windows: ResMut<Windows>) {
+pub fn handle_window(
+    mut keys: ResMut<Input<KeyCode>>,
+    mut windows: ResMut<Windows>,
+    mut settings: ResMut<DisplaySettings>,
+    mut resized: EventReader<WindowResized>,
+    mut pending: Local<PendingResize>,
+    options: Res<LaunchOptions>,
+    time: Res<Time>,
+) {
     let primary = windows.primary_mut();
     // Toggle fullscreen:
     if keys.clear_just_pressed(KeyCode::F11) {
-        primary.set_mode(if primary.mode() != WindowMode::Windowed {
-            WindowMode::Windowed
-        } else {
-            WindowMode::BorderlessFullscreen
-        });
+        let mode = settings.toggle_fullscreen(primary.mode());
+        primary.set_mode(mode);
+    }
+    // Remember the size of the window, unless it was given on the command line.
+    let primary_id = primary.id();
+    let windowed = primary.mode() == WindowMode::Windowed;
+    for event in resized.iter().filter(|event| event.id == primary_id) {
+        if windowed && options.window_size.is_none() {
+            pending.size = Some((event.width.round() as u32, event.height.round() as u32));
+            pending.seconds_passed = 0.;
+        }
+    }
+    if let Some(size) = pending.size {
+        pending.seconds_passed += time.delta_seconds();
+        if pending.seconds_passed >= RESIZE_SAVE_DELAY {
+            pending.size = None;
+            settings.resize(size);
+        }
     }
 }
 
-/////Responds to window resize events. Recreates the camera with the new dimensions.
-// fn resize_camera(world: &mut World) {
-//     world.exec(
-//         |data: (
-//             Entities<'_>,
-//             WriteStorage<'_, Camera>,
-//             WriteStorage<'_, Transform>,
-//             WriteStorage<'_, Parent>,
-//             ReadStorage<'_, CameraFrame>,
-//             ReadExpect<'_, ScreenDimensions>,
-//         )| {
-//             let (entities, mut cameras, mut transforms, mut parents, camera_frames, screen_dimens) =
-//                 data;
-//             let frame = (&*entities, &camera_frames)
-//                 .join()
-//                 .map(|(entity, _)| entity)
-//                 .next();
-//             let cam = (&*entities, &cameras)
-//                 .join()
-//                 .map(|(entity, _)| entity)
-//                 .next();
-//             if let Some(frame) = frame {
-//                 if let Some(cam) = cam {
-//                     entities
-//                         .delete(cam)
-//                         .expect("Trying to resize, but failed to delete camera.");
-//                 }
-//                 if screen_dimens.x() > f32::EPSILON && screen_dimens.y() > f32::EPSILON {
-//                     entities
-//                         .build_entity()
-//                         .with(Parent { entity: frame }, &mut parents)
-//                         .with(
-//                             Camera::standard_2d(screen_dimens.x(), screen_dimens.y()),
-//                             &mut cameras,
-//                         )
-//                         .with(Transform::default(), &mut transforms)
-//                         .build();
-//                 }
-//             }
-//         },
-//     );
-// }
+/// Windows that should be fullscreen on another monitor than the primary one are opened in
+/// windowed mode on that monitor. This switches them to fullscreen once they are there.
+pub fn apply_display_mode(
+    settings: Res<DisplaySettings>,
+    options: Res<LaunchOptions>,
+    mut windows: ResMut<Windows>,
+) {
+    let mode = settings.window_mode(&options);
+    if settings.monitor.is_some() && mode != WindowMode::Windowed {
+        windows.primary_mut().set_mode(mode);
+    }
+}
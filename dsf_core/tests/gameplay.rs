@@ -0,0 +1,205 @@
+//! Plays small levels headless and checks that the rules of the game hold.
+
+use bevy::prelude::KeyCode;
+
+use harness::{Harness, LevelBuilder};
+
+mod harness;
+
+/// A level with a solid floor at y = 0 and the player standing on it at x = 0. Walls on both sides
+/// keep the player from walking around the world.
+fn floor() -> LevelBuilder {
+    LevelBuilder::new(-10, -4, 24, 16)
+        .row(-10, 13, 0, "Block1")
+        .column(-6, 1, 4, "Block1")
+        .column(10, 1, 4, "Block1")
+        .tile(0, 1, "Player")
+}
+
+#[test]
+fn player_walks_and_stops_at_wall() {
+    let mut game = floor().play();
+    let start = game.player().pos;
+    game.hold(KeyCode::D, 3.);
+    let end = game.player().pos;
+    assert_eq!(end.y, start.y, "player should stay on the floor");
+    assert_eq!(end.x, 8, "player should stop against the wall");
+    assert!(!game.is_dying());
+}
+
+#[test]
+fn pickaxe_digs_through_the_floor() {
+    let mut game = floor()
+        .row(1, 2, 0, "Block2")
+        .row(1, 2, -1, "Block2")
+        .row(-10, 13, -2, "Block1")
+        .tile(1, 1, "ToolPickaxe")
+        .play();
+    game.run(0.1);
+    game.tap(KeyCode::Q);
+    for x in 1..=2 {
+        assert_eq!(game.tile_at(x, 0), None);
+        assert_eq!(game.tile_at(x, -1), None);
+    }
+    assert_eq!(game.tile_at(3, 0).as_deref(), Some("Block1"));
+    game.hold(KeyCode::D, 0.5);
+    game.run(0.5);
+    assert_eq!(game.player().pos.y, -1, "player should drop into the hole");
+    assert!(!game.is_dying());
+}
+
+#[test]
+fn pickaxe_cannot_break_invulnerable_blocks() {
+    let mut game = floor().tile(1, 1, "ToolPickaxe").play();
+    game.run(0.1);
+    game.tap(KeyCode::Q);
+    assert_eq!(game.tile_at(1, 0).as_deref(), Some("Block1"));
+    assert_eq!(game.tile_at(2, 0).as_deref(), Some("Block1"));
+}
+
+#[test]
+fn player_jumps_while_holding_a_tool() {
+    let mut game = floor().tile(1, 1, "ToolPickaxe").play();
+    game.run(0.1);
+    game.tap(KeyCode::Space);
+    game.run(0.2);
+    assert!(game.player().pos.y > 1, "player should be in the air");
+    assert_eq!(game.tile_at(1, 0).as_deref(), Some("Block1"));
+}
+
+#[test]
+fn falling_block_does_not_rest_on_a_key() {
+    let mut game = floor().tile(4, 1, "Key").tile(4, 3, "FallingBlock").play();
+    game.run(1.);
+    assert_eq!(
+        game.tile_at(4, 3),
+        None,
+        "the block should fall past the key"
+    );
+}
+
+#[test]
+fn platform_moves_through_a_key() {
+    let mut game = floor()
+        .tile(2, 3, "Platform")
+        .path(2, 3, &[(2, 9)])
+        .tile(2, 5, "Key")
+        .play();
+    let reached_the_top = (0..5 * harness::TICKS_PER_SECOND).any(|_| {
+        game.tick();
+        game.tile_at(2, 9).is_some()
+    });
+    assert!(reached_the_top, "the key should not stop the platform");
+    assert_eq!(
+        game.tile_at(2, 5).as_deref(),
+        Some("Key"),
+        "the key should be back once the platform has passed"
+    );
+}
+
+#[test]
+fn trap_closes_behind_the_player() {
+    let mut game = floor().column(4, 1, 2, "TrapWall").play();
+    game.hold(KeyCode::D, 1.);
+    assert!(
+        game.player().pos.x > 4,
+        "player should walk through the trap"
+    );
+    game.run(3.);
+    assert_eq!(game.tile_at(4, 2).as_deref(), Some("Block2"));
+    assert_eq!(game.tile_at(4, 1).as_deref(), Some("Block2"));
+    game.hold(KeyCode::A, 2.);
+    assert_eq!(
+        game.player().pos.x,
+        5,
+        "the closed trap should block the way back"
+    );
+}
+
+#[test]
+fn revolving_door_turns_after_letting_player_through() {
+    let mut game = floor()
+        .column(4, 1, 2, "RevolvingDoorFacingPositive")
+        .play();
+    game.hold(KeyCode::D, 4.);
+    assert_eq!(
+        game.player().pos.x,
+        8,
+        "player should pass through the door"
+    );
+    assert_eq!(
+        game.tile_at(4, 2).as_deref(),
+        Some("RevolvingDoorFacingNegative")
+    );
+    game.hold(KeyCode::A, 4.);
+    assert_eq!(
+        game.player().pos.x,
+        -5,
+        "the turned door should let the player back"
+    );
+}
+
+#[test]
+fn revolving_door_blocks_player_in_the_other_direction() {
+    let mut game = floor()
+        .column(4, 1, 2, "RevolvingDoorFacingNegative")
+        .play();
+    game.hold(KeyCode::D, 4.);
+    assert_eq!(
+        game.player().pos.x,
+        2,
+        "player should be stopped by the door"
+    );
+}
+
+#[test]
+fn spikes_kill_the_player() {
+    let mut game = Harness::from_ron(
+        r#"(
+            world_bounds: ((pos: ((-4, -2)), dimens: ((12, 8)))),
+            tiles: {
+                ((-4, 0)): "Block1", ((-3, 0)): "Block1", ((-2, 0)): "Block1",
+                ((-1, 0)): "Block1", ((0, 0)): "Block1", ((1, 0)): "Block1",
+                ((2, 0)): "Block1", ((3, 0)): "Block1", ((4, 0)): "Block1",
+                ((5, 0)): "Block1", ((6, 0)): "Block1", ((7, 0)): "Block1",
+                ((0, 1)): "Player",
+                ((4, 1)): "Spikes",
+            },
+        )"#,
+    );
+    assert!(!game.is_dying());
+    game.hold(KeyCode::D, 1.);
+    assert!(game.is_dying());
+}
+
+#[test]
+fn collecting_all_keys_and_reaching_the_door_wins() {
+    let mut game = Harness::from_ascii(
+        "
+        #.............#
+        #.........DDDD#
+        #.........DDDD#
+        #PP.KK....DDDD#
+        #PP.KK....DDDD#
+        ###############
+        ",
+    );
+    assert_eq!(game.win_condition().nr_keys_left(), 1);
+    game.hold(KeyCode::D, 3.);
+    assert_eq!(game.win_condition().nr_keys_left(), 0);
+    assert_eq!(game.win_condition().nr_keys_collected(), 1);
+    assert!(game.has_won());
+}
+
+#[test]
+fn door_stays_closed_while_keys_are_left() {
+    let mut game = floor()
+        .tile(-6, 1, "Key")
+        .tile(3, 1, "Key")
+        .tile(6, 1, "Door")
+        .play();
+    assert_eq!(game.win_condition().nr_keys_left(), 2);
+    game.hold(KeyCode::D, 3.);
+    assert_eq!(game.win_condition().nr_keys_left(), 1);
+    assert!(!game.has_won());
+}
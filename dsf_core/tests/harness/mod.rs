@@ -0,0 +1,258 @@
+//! Runs the gameplay of a level headless, without a window, rendering or audio.
+//!
+//! The harness steps the game at a fixed rate and feeds it scripted keyboard input, so every run
+//! of a test plays out exactly the same. Afterwards, the state of the level can be inspected.
+
+use std::time::Duration;
+
+use bevy::ecs::system::CommandQueue;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::{create_time_channels, TimeSender};
+use bevy::utils::Instant;
+use iyes_loopless::prelude::*;
+
+use dsf_core::audio::sound_event::SoundEvent;
+use dsf_core::config::movement_config::MovementConfig;
+use dsf_core::config::settings::difficulty_settings::DifficultySettings;
+use dsf_core::levels::ascii::{from_ascii, AsciiLegend};
+use dsf_core::levels::level_save::LevelSave;
+use dsf_core::levels::tiles::tile_defs::TileDefinitions;
+use dsf_core::levels::tiles::tilemap::TileMap;
+use dsf_core::levels::world_bounds::WorldBounds;
+use dsf_core::loading::assets::{AssetStorage, SpriteType};
+use dsf_core::loading::levels::load_level_system::spawn_level;
+use dsf_core::states::{AppState, GameplayPlugin, LevelLoaded};
+use dsf_core::systems::checkpoint::CheckpointSave;
+use dsf_core::systems::death::cause::DeathLog;
+use dsf_core::systems::death::death_anim::Dying;
+use dsf_core::systems::death::lives::Lives;
+use dsf_core::systems::motion::structs::coords::Coords;
+use dsf_core::systems::motion::structs::player::Player;
+use dsf_core::systems::motion::structs::pos::Pos;
+use dsf_core::systems::scoring::LevelStats;
+use dsf_core::systems::win_checking::WinCondition;
+use dsf_core::systems::win_handling::WinResource;
+
+/// The game is stepped at this rate, regardless of how fast the test actually runs.
+pub const TICKS_PER_SECOND: u32 = 60;
+
+/// The configs are read at compile time, so the tests do not depend on the working directory or
+/// on any user settings.
+const TILE_DEFINITIONS: &str = include_str!("../../../assets/world/tile_definitions.ron");
+const MOVEMENT_CONFIG: &str = include_str!("../../../assets/config/movement.ron");
+const DIFFICULTY_SETTINGS: &str =
+    include_str!("../../../assets/config/default_settings/difficulty.ron");
+
+/// A level that is being played headless.
+pub struct Harness {
+    pub app: App,
+    time: TimeSender,
+    now: Instant,
+}
+
+impl Harness {
+    /// Starts playing the level described by the given RON string, in the same format as the level
+    /// files.
+    pub fn from_ron(level: &str) -> Self {
+        let level = ron::de::from_str::<LevelSave>(level).expect("Unable to deserialise level");
+        Harness::new(&level)
+    }
+
+    /// Starts playing the level drawn in the given ASCII map, using the default legend. The lower
+    /// left corner of the map is at (0, 0).
+    pub fn from_ascii(map: &str) -> Self {
+        let level = from_ascii(
+            map,
+            Pos::new(0, 0),
+            &AsciiLegend::default(),
+            &tile_definitions(),
+        )
+        .unwrap_or_else(|error| panic!("Unable to read ASCII map: {}", error));
+        Harness::new(&level)
+    }
+
+    /// Starts playing the given level.
+    pub fn new(level: &LevelSave) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(InputPlugin)
+            .add_loopless_state(AppState::InGame)
+            .add_event::<SoundEvent>()
+            .add_plugin(GameplayPlugin);
+
+        let difficulty = ron::de::from_str::<DifficultySettings>(DIFFICULTY_SETTINGS)
+            .expect("Unable to deserialise DifficultySettings");
+        app.insert_resource(Lives::new(&difficulty))
+            .insert_resource(difficulty)
+            .insert_resource(
+                ron::de::from_str::<MovementConfig>(MOVEMENT_CONFIG)
+                    .expect("Unable to deserialise MovementConfig"),
+            )
+            .init_resource::<WinCondition>()
+            .init_resource::<LevelStats>()
+            .init_resource::<DeathLog>()
+            .init_resource::<CheckpointSave>();
+
+        // There are no textures, every sprite falls back to the default handle.
+        let mut storage = AssetStorage::default();
+        storage.put_atlas(SpriteType::NotFound, Handle::default());
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &app.world);
+        spawn_level(&mut commands, &storage, level, tile_definitions());
+        queue.apply(&mut app.world);
+        app.insert_resource(storage);
+        app.world.send_event(LevelLoaded);
+
+        let (time, receiver) = create_time_channels();
+        app.insert_resource(receiver);
+        let mut harness = Harness {
+            app,
+            time,
+            now: Instant::now(),
+        };
+        // The very first frame always has a delta of zero; it sets up the level.
+        harness.tick();
+        harness
+    }
+
+    /// Advances the game by a single tick.
+    pub fn tick(&mut self) {
+        self.now += Duration::from_secs(1) / TICKS_PER_SECOND;
+        self.time.0.send(self.now).expect("Unable to send the time");
+        self.app.update();
+    }
+
+    /// Advances the game by the given number of seconds.
+    pub fn run(&mut self, seconds: f32) {
+        for _ in 0..ticks(seconds) {
+            self.tick();
+        }
+    }
+
+    /// Starts holding down the given key. It stays down until it is released.
+    pub fn press(&mut self, key: KeyCode) {
+        self.send_key(key, ButtonState::Pressed);
+    }
+
+    /// Lets go of the given key.
+    pub fn release(&mut self, key: KeyCode) {
+        self.send_key(key, ButtonState::Released);
+    }
+
+    /// Holds down the given key for the given number of seconds, then lets go of it.
+    pub fn hold(&mut self, key: KeyCode, seconds: f32) {
+        self.press(key);
+        self.run(seconds);
+        self.release(key);
+        self.tick();
+    }
+
+    /// Presses the given key for a single tick.
+    pub fn tap(&mut self, key: KeyCode) {
+        self.press(key);
+        self.tick();
+        self.release(key);
+        self.tick();
+    }
+
+    fn send_key(&mut self, key: KeyCode, state: ButtonState) {
+        self.app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key),
+            state,
+        });
+    }
+
+    /// The position and size of the player.
+    pub fn player(&mut self) -> Coords {
+        *self
+            .app
+            .world
+            .query_filtered::<&Coords, With<Player>>()
+            .single(&self.app.world)
+    }
+
+    /// True iff the player is dying.
+    pub fn is_dying(&mut self) -> bool {
+        self.app
+            .world
+            .query_filtered::<(), (With<Player>, With<Dying>)>()
+            .iter(&self.app.world)
+            .next()
+            .is_some()
+    }
+
+    /// True iff the player has finished the level.
+    pub fn has_won(&self) -> bool {
+        self.app.world.contains_resource::<WinResource>()
+    }
+
+    pub fn win_condition(&self) -> &WinCondition {
+        self.app.world.resource::<WinCondition>()
+    }
+
+    pub fn tile_map(&self) -> &TileMap {
+        self.app.world.resource::<TileMap>()
+    }
+
+    /// The key of the tile definition of the tile anchored at the given position, if any.
+    pub fn tile_at(&self, x: i32, y: i32) -> Option<String> {
+        self.tile_map().get_tile_def_key(&Pos::new(x, y)).cloned()
+    }
+}
+
+/// Helps put together small levels for tests.
+pub struct LevelBuilder {
+    level: LevelSave,
+}
+
+impl LevelBuilder {
+    /// An empty level with the given bounds.
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        LevelBuilder {
+            level: LevelSave {
+                world_bounds: WorldBounds::new(x, y, width, height),
+                ..default()
+            },
+        }
+    }
+
+    /// Puts the tile with the given key at the given position.
+    pub fn tile(mut self, x: i32, y: i32, key: &str) -> Self {
+        self.level.tiles.insert(Pos::new(x, y), key.to_string());
+        self
+    }
+
+    /// Puts a horizontal row of the given tile from `from` up to and including `to`.
+    pub fn row(self, from: i32, to: i32, y: i32, key: &str) -> Self {
+        (from..=to).fold(self, |builder, x| builder.tile(x, y, key))
+    }
+
+    /// Puts a vertical column of the given tile from `from` up to and including `to`.
+    pub fn column(self, x: i32, from: i32, to: i32, key: &str) -> Self {
+        (from..=to).fold(self, |builder, y| builder.tile(x, y, key))
+    }
+
+    /// Gives the moving platform at the given position a route through the given waypoints.
+    pub fn path(mut self, x: i32, y: i32, waypoints: &[(i32, i32)]) -> Self {
+        let route = waypoints.iter().map(|(x, y)| Pos::new(*x, *y)).collect();
+        self.level.paths.insert(Pos::new(x, y), route);
+        self
+    }
+
+    pub fn play(self) -> Harness {
+        Harness::new(&self.level)
+    }
+}
+
+fn tile_definitions() -> TileDefinitions {
+    ron::de::from_str::<TileDefinitions>(TILE_DEFINITIONS)
+        .expect("Unable to deserialise TileDefinitions")
+}
+
+fn ticks(seconds: f32) -> u32 {
+    (seconds * TICKS_PER_SECOND as f32).round() as u32
+}
@@ -1,3 +1,4 @@
 pub mod cursor;
 pub mod painted_tile;
 pub mod selection;
+pub mod wire_label;
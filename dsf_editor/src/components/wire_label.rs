@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+/// Entities with this component are the text labels in the editor that show which signal a tile
+/// is wired to, or where the waypoints of a platform path are.
+#[derive(Clone, Copy, Debug, Default, Component)]
+pub struct WireLabel;
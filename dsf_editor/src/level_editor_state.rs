@@ -9,6 +9,7 @@ use crate::systems::selection::selection_system;
 use crate::systems::setup::{init_instructions, init_misc};
 use crate::systems::tile_paint::tile_paint_system;
 use crate::systems::update_background::update_background;
+use crate::systems::wire_labels::paint_wire_labels;
 use bevy::prelude::*;
 use dsf_core::camera::camera_systems::{camera_control, camera_follow_focal_point};
 use dsf_core::camera::create_camera::create_camera;
@@ -46,6 +47,7 @@ impl Plugin for LevelEditorState {
                 .with_system(update_background) //.after(cursor_controls)) TODO
                 .with_system(selection_system) //.after(cursor_controls)) TODO
                 .with_system(tile_paint_system) //.after(selection_system)) TODO
+                .with_system(paint_wire_labels)
                 .into(),
         )
         .add_exit_system_set(
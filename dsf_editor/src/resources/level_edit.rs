@@ -14,13 +14,23 @@ pub struct LevelEdit {
     /// A list of tile positions that are marked are dirty and must be redrawn.
     /// Whenever you add, update or remove a tile in the editor, you must mark it as dirty.
     pub dirty: HashSet<Pos>,
+    /// Mapping of tile anchor positions to the name of the signal that tile is wired to.
+    pub wiring: HashMap<Pos, String>,
+    /// Mapping of moving platform anchor positions to the waypoints that platform travels along.
+    pub paths: HashMap<Pos, Vec<Pos>>,
+    /// Set whenever the wiring or the paths change, so the labels know to redraw themselves.
+    pub labels_dirty: bool,
 }
 
+/// The signal names that the editor cycles through when wiring up a tile.
+const SIGNAL_NAMES: [&str; 8] = ["A", "B", "C", "D", "E", "F", "G", "H"];
+
 /// Implements the standard converter from `LevelEdit` to `LevelSave`. In other words: convert a level
 /// from a format that the editor uses, to the format that levels are stored in on disk.
 ///
 /// Note that only `TileDefKey` entries are saved, Dummy entries are discarded, because they can be
-/// derived from the primary entries and only exist for faster lookup.
+/// derived from the primary entries and only exist for faster lookup. Wires and paths attached to
+/// positions that no longer contain a tile are discarded as well.
 impl From<LevelEdit> for LevelSave {
     fn from(mut item: LevelEdit) -> Self {
         let mut map = HashMap::new();
@@ -29,9 +39,21 @@ impl From<LevelEdit> for LevelSave {
                 map.insert(pos, tile_def_key);
             }
         });
+        let wiring = item
+            .wiring
+            .drain()
+            .filter(|(pos, _)| map.contains_key(pos))
+            .collect();
+        let paths = item
+            .paths
+            .drain()
+            .filter(|(pos, _)| map.contains_key(pos))
+            .collect();
         LevelSave {
             world_bounds: item.tile_map.world_bounds,
             tiles: map,
+            wiring,
+            paths,
         }
     }
 }
@@ -42,9 +64,59 @@ impl LevelEdit {
         LevelEdit {
             tile_map: TileMap::for_editing(&level_save, tile_defs),
             dirty: initial_dirty,
+            wiring: level_save.wiring,
+            paths: level_save.paths,
+            labels_dirty: true,
         }
     }
 
+    /// Wires the tile at the given position to the next signal in the list of signal names.
+    /// After the last signal name, the wire is removed again.
+    /// Does nothing if there is no tile at the given position.
+    pub(crate) fn cycle_signal(&mut self, pos: &Pos) {
+        if let Some(anchor) = self.tile_map.get_actual_pos(pos) {
+            let next = match self.wiring.get(&anchor) {
+                None => SIGNAL_NAMES.first(),
+                Some(current) => SIGNAL_NAMES
+                    .iter()
+                    .position(|name| name == current)
+                    .and_then(|index| SIGNAL_NAMES.get(index + 1)),
+            };
+            if let Some(next) = next {
+                self.wiring.insert(anchor, next.to_string());
+            } else {
+                self.wiring.remove(&anchor);
+            }
+            self.labels_dirty = true;
+        }
+    }
+
+    /// Adds a waypoint to the path of the moving platform at the given position.
+    /// Does nothing if there is no moving platform at that position.
+    pub(crate) fn add_waypoint(&mut self, platform_pos: &Pos, waypoint: Pos) {
+        if let Some(anchor) = self.platform_anchor(platform_pos) {
+            self.paths.entry(anchor).or_default().push(waypoint);
+            self.labels_dirty = true;
+        }
+    }
+
+    /// Removes the entire path of the moving platform at the given position.
+    pub(crate) fn clear_path(&mut self, platform_pos: &Pos) {
+        if let Some(anchor) = self.platform_anchor(platform_pos) {
+            self.paths.remove(&anchor);
+            self.labels_dirty = true;
+        }
+    }
+
+    /// Returns the anchor position of the moving platform covering the given position, if any.
+    fn platform_anchor(&self, pos: &Pos) -> Option<Pos> {
+        self.tile_map.get_actual_pos(pos).filter(|anchor| {
+            self.tile_map
+                .get_tile(anchor)
+                .is_some_and(|tile| tile.platform().is_some())
+        })
+    }
+
     /// Empty the set of dirty positions and collect them into a new collection.
     ///
     /// The process of re-collecting them before draining them (or otherwise iterating over them)
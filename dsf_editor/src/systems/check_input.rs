@@ -14,13 +14,15 @@ use iyes_loopless::prelude::NextState;
 /// - Changing what tile is on the brush.
 /// - Toggling the copy-air flag.
 /// - Toggling the force-place flag.
+/// - Wiring the tile under the cursor to a signal.
+/// - Adding waypoints to the path of a moving platform, or clearing that path.
 ///
 pub fn check_editor_input(
     mut commands: Commands,
     mut keys: ResMut<Input<KeyCode>>,
     mut writer: EventWriter<RefreshPreviewsEvent>,
     mut status: ResMut<EditorStatus>,
-    level_edit: Res<LevelEdit>,
+    mut level_edit: ResMut<LevelEdit>,
 ) {
     if keys.clear_just_pressed(KeyCode::LBracket) {
         let _new_key = status.brush.select_previous();
@@ -38,6 +40,18 @@ pub fn check_editor_input(
         status.copy_air ^= true;
         writer.send(RefreshPreviewsEvent);
     }
+    if keys.clear_just_pressed(KeyCode::N) {
+        let cursor = status.selection.end;
+        level_edit.cycle_signal(&cursor);
+    }
+    if keys.clear_just_pressed(KeyCode::P) {
+        // The selection starts on the platform, the cursor marks the waypoint.
+        let waypoint = status.selection.end;
+        level_edit.add_waypoint(&status.selection.start, waypoint);
+    }
+    if keys.clear_just_pressed(KeyCode::O) {
+        level_edit.clear_path(&status.selection.start);
+    }
     if keys.pressed(KeyCode::LControl) && keys.clear_just_pressed(KeyCode::S) {
         auto_save(&level_edit);
     }
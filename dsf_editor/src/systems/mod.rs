@@ -7,3 +7,4 @@ pub mod selection;
 pub mod setup;
 pub mod tile_paint;
 pub mod update_background;
+pub mod wire_labels;
@@ -1,8 +1,6 @@
 use bevy::prelude::*;
 
-use dsf_core::levels::tiles::tile_defs::Archetype;
 use dsf_core::loading::assets::AssetStorage;
-use dsf_core::systems::motion::structs::direction::Direction1D;
 
 use crate::components::cursor::PreviewGhostTag;
 use crate::components::painted_tile::PaintedTile;
@@ -53,7 +51,10 @@ pub fn refresh_previews(
         sprite.color = if blueprint_dry_run.to_be_removed.contains(&painted_tile.pos) {
             Color::rgba(1., 0., 0., 1.0)
         } else {
-            Color::rgba(1., 1., 1., 1.0)
+            level_edit
+                .tile_map
+                .get_tile(&painted_tile.pos)
+                .map_or(Color::WHITE, |tile_def| tile_def.color())
         };
     }
     // First delete all existing previews:
@@ -67,8 +68,9 @@ pub fn refresh_previews(
         .for_each(|(pos, _, key)| {
             let tile_def = level_edit.tile_map.tile_defs.get(key);
             let atlas = storage.get_atlas(&tile_def.get_preview().0);
-            let flip_x =
-                tile_def.archetype == Some(Archetype::RevolvingDoor(Direction1D::Negative));
+            let flip_x = tile_def
+                .revolving()
+                .is_some_and(|door| door.facing.is_negative());
             commands
                 .spawn()
                 .insert_bundle(SpriteSheetBundle {
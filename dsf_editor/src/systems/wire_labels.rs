@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use bevy::text::Text2dSize;
+
+use dsf_core::levels::tiles::tile_defs::DepthLayer;
+use dsf_core::systems::motion::structs::pos::Pos;
+
+use crate::components::wire_label::WireLabel;
+use crate::resources::level_edit::LevelEdit;
+
+/// Whenever the wiring or the platform paths change, clears all labels and draws them again.
+/// Each wired tile is labelled with the name of the signal it is wired to. Each waypoint of a
+/// platform path is labelled with its number in the path.
+pub fn paint_wire_labels(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    mut level_edit: ResMut<LevelEdit>,
+    query: Query<Entity, With<WireLabel>>,
+) {
+    if !level_edit.labels_dirty {
+        return;
+    }
+    level_edit.labels_dirty = false;
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let font = assets.load("fonts/square.ttf");
+    for (pos, signal) in level_edit.wiring.iter() {
+        spawn_label(&mut commands, &font, pos, signal, Color::YELLOW);
+    }
+    for waypoints in level_edit.paths.values() {
+        for (index, pos) in waypoints.iter().enumerate() {
+            spawn_label(
+                &mut commands,
+                &font,
+                pos,
+                &(index + 1).to_string(),
+                Color::CYAN,
+            );
+        }
+    }
+}
+
+fn spawn_label(commands: &mut Commands, font: &Handle<Font>, pos: &Pos, text: &str, color: Color) {
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 60.,
+        color,
+    };
+    let text_alignment = TextAlignment {
+        vertical: VerticalAlign::Center,
+        horizontal: HorizontalAlign::Center,
+    };
+    let transform = Transform::from_xyz(
+        pos.x as f32 + 0.5,
+        pos.y as f32 + 0.5,
+        DepthLayer::UiElements.z(),
+    )
+    .with_scale(Vec3::new(1. / 128., 1. / 128., 1.));
+    commands
+        .spawn()
+        .insert_bundle(Text2dBundle {
+            text: Text::from_section(text, text_style).with_alignment(text_alignment),
+            text_2d_size: Text2dSize {
+                size: Vec2::new(1., 1.),
+            },
+            transform,
+            ..default()
+        })
+        .insert(WireLabel);
+}
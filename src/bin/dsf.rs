@@ -0,0 +1,276 @@
+#![forbid(unsafe_code)]
+//! Command line tool for working with levels, without starting the game.
+//!
+//! Like the game itself, it expects to be run from the directory that contains `assets/`.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use dsf_core::level_select::create_default_adventure::write_default_adventure;
+use dsf_core::levels::ascii::{from_ascii, to_ascii, AsciiLegend};
+use dsf_core::levels::level_save::LevelSave;
+use dsf_core::levels::thumbnail::ThumbnailRenderer;
+use dsf_core::levels::tiled::{from_tiled_json, from_tmx, to_tiled_json, to_tmx, TiledMapping};
+use dsf_core::levels::tiles::tile_defs::{Archetype, TileDefinitions};
+use dsf_core::levels::validation::validate;
+use dsf_core::loading::levels::load_level_system::try_load_tile_definitions;
+use dsf_core::systems::motion::structs::pos::Pos;
+use dsf_core::util::files::{get_levels_dir, get_world_dir, serialise_ron};
+
+const USAGE: &str = "\
+Usage: dsf <command> [arguments]
+
+Commands:
+    validate [LEVEL...]               Check levels for problems. Checks all levels if none are given.
+    convert IN OUT [--origin X,Y]     Convert a level between formats. The format follows from the
+                                      extension: .ron, .txt (ASCII), .tmx or .tmj/.json (Tiled).
+                                      The origin is the lower left corner of ASCII input.
+    thumbnail LEVEL PNG [--tile-pixels N]
+                                      Render a picture of the level.
+    stats LEVEL...                    Print what the level is made of.
+    adventure                         Regenerate adventures/default.ron from all levels.";
+
+/// The number of pixels per tile in thumbnails, unless told otherwise.
+const DEFAULT_TILE_PIXELS: u32 = 16;
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let result = match args.split_first() {
+        Some((command, args)) => match command.as_str() {
+            "validate" => validate_levels(args),
+            "convert" => convert(args),
+            "thumbnail" => thumbnail(args),
+            "stats" => stats(args),
+            "adventure" => adventure(args),
+            "help" | "--help" | "-h" => {
+                println!("{}", USAGE);
+                Ok(())
+            }
+            _ => Err(format!("Unknown command {:?}.\n\n{}", command, USAGE)),
+        },
+        None => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn validate_levels(args: &[String]) -> Result<(), String> {
+    let paths = if args.is_empty() {
+        level_files()?
+    } else {
+        args.iter().map(PathBuf::from).collect()
+    };
+    let tile_defs = try_load_tile_definitions()?;
+    let mut failed = 0;
+    for path in &paths {
+        let problems = match read_level(path, &tile_defs, Pos::default()) {
+            Ok(level) => validate(&level, &tile_defs)
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            Err(error) => vec![error],
+        };
+        if problems.is_empty() {
+            println!("ok      {}", path.display());
+        } else {
+            failed += 1;
+            println!("FAILED  {}", path.display());
+            problems
+                .iter()
+                .for_each(|problem| println!("        {}", problem));
+        }
+    }
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} levels have problems.",
+            failed,
+            paths.len()
+        ))
+    }
+}
+
+fn convert(args: &[String]) -> Result<(), String> {
+    let (positional, origin) = take_option(args, "--origin")?;
+    let origin = origin.map_or(Ok(Pos::default()), |origin| parse_pos(&origin))?;
+    let (input, output) = match positional.as_slice() {
+        [input, output] => (Path::new(input), Path::new(output)),
+        _ => return Err(USAGE.to_string()),
+    };
+    let tile_defs = try_load_tile_definitions()?;
+    let level = read_level(input, &tile_defs, origin)?;
+    write_level(output, &level, &tile_defs)
+}
+
+fn thumbnail(args: &[String]) -> Result<(), String> {
+    let (positional, tile_pixels) = take_option(args, "--tile-pixels")?;
+    let tile_pixels = tile_pixels.map_or(Ok(DEFAULT_TILE_PIXELS), |pixels| {
+        pixels
+            .parse::<u32>()
+            .map_err(|_| format!("Not a number of pixels: {:?}", pixels))
+    })?;
+    let (input, output) = match positional.as_slice() {
+        [input, output] => (Path::new(input), Path::new(output)),
+        _ => return Err(USAGE.to_string()),
+    };
+    let tile_defs = try_load_tile_definitions()?;
+    let level = read_level(input, &tile_defs, Pos::default())?;
+    let renderer = ThumbnailRenderer::load(tile_defs).map_err(|error| error.to_string())?;
+    renderer
+        .render(&level, tile_pixels)
+        .save(output)
+        .map_err(|error| format!("Unable to write {:?}: {}", output, error))
+}
+
+fn stats(args: &[String]) -> Result<(), String> {
+    if args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    let tile_defs = try_load_tile_definitions()?;
+    for path in args.iter().map(Path::new) {
+        let level = read_level(path, &tile_defs, Pos::default())?;
+        let mut counts = BTreeMap::new();
+        level
+            .tiles
+            .values()
+            .for_each(|key| *counts.entry(key.as_str()).or_insert(0) += 1);
+        let count_archetype = |matches: fn(&Archetype) -> bool| {
+            level
+                .tiles
+                .values()
+                .filter_map(|key| tile_defs.map.get(key)?.archetype.as_ref())
+                .filter(|archetype| matches(archetype))
+                .count()
+        };
+        let bounds = &level.world_bounds;
+        println!("{}", path.display());
+        println!(
+            "    size:     {} x {} at ({}, {})",
+            bounds.width(),
+            bounds.height(),
+            bounds.x(),
+            bounds.y()
+        );
+        println!("    tiles:    {}", level.tiles.len());
+        println!(
+            "    keys:     {}",
+            count_archetype(|archetype| matches!(archetype, Archetype::Key))
+        );
+        println!(
+            "    tools:    {}",
+            count_archetype(|archetype| matches!(archetype, Archetype::Tool(_)))
+        );
+        println!(
+            "    spawners: {}",
+            count_archetype(|archetype| matches!(archetype, Archetype::Spawner(_)))
+        );
+        println!("    wires:    {}", level.wiring.len());
+        println!("    paths:    {}", level.paths.len());
+        for (key, count) in counts {
+            println!("    {:>6}  {}", count, key);
+        }
+    }
+    Ok(())
+}
+
+fn adventure(args: &[String]) -> Result<(), String> {
+    if !args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    for problem in write_default_adventure()? {
+        eprintln!("{}", problem);
+    }
+    Ok(())
+}
+
+/// Reads a level in any of the supported formats, based on the file extension. The origin is only
+/// used for ASCII maps, which do not store one.
+fn read_level(path: &Path, tile_defs: &TileDefinitions, origin: Pos) -> Result<LevelSave, String> {
+    let data = fs::read_to_string(path).map_err(|error| format!("{:?}: {}", path, error))?;
+    let level = match extension(path).as_str() {
+        "ron" => ron::de::from_str::<LevelSave>(&data).map_err(|error| error.to_string()),
+        "txt" => from_ascii(&data, origin, &AsciiLegend::default(), tile_defs)
+            .map_err(|error| error.to_string()),
+        "tmx" => from_tmx(&data, &tiled_mapping()?).map_err(|error| error.to_string()),
+        "tmj" | "json" => {
+            from_tiled_json(&data, &tiled_mapping()?).map_err(|error| error.to_string())
+        }
+        other => Err(format!("unknown level format {:?}", other)),
+    };
+    level.map_err(|error| format!("{:?}: {}", path, error))
+}
+
+fn write_level(path: &Path, level: &LevelSave, tile_defs: &TileDefinitions) -> Result<(), String> {
+    let data = match extension(path).as_str() {
+        "ron" => serialise_ron(level).map_err(|error| error.to_string()),
+        "txt" => {
+            to_ascii(level, &AsciiLegend::default(), tile_defs).map_err(|error| error.to_string())
+        }
+        "tmx" => to_tmx(level, &tiled_mapping()?).map_err(|error| error.to_string()),
+        "tmj" | "json" => {
+            to_tiled_json(level, &tiled_mapping()?).map_err(|error| error.to_string())
+        }
+        other => Err(format!("unknown level format {:?}", other)),
+    }
+    .map_err(|error| format!("{:?}: {}", path, error))?;
+    fs::write(path, data).map_err(|error| format!("{:?}: {}", path, error))
+}
+
+fn tiled_mapping() -> Result<TiledMapping, String> {
+    TiledMapping::load_from_file(&get_world_dir().join("tiled_mapping.ron"))
+        .map_err(|error| error.to_string())
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// All level files in the levels directory, in alphabetical order.
+fn level_files() -> Result<Vec<PathBuf>, String> {
+    let mut paths = fs::read_dir(get_levels_dir())
+        .map_err(|error| format!("Unable to read the levels directory: {}", error))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Splits off the value of the given option, if it is present.
+fn take_option(args: &[String], name: &str) -> Result<(Vec<String>, Option<String>), String> {
+    let mut positional = Vec::new();
+    let mut value = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            value = Some(
+                args.next()
+                    .ok_or_else(|| format!("{} needs a value.", name))?
+                    .clone(),
+            );
+        } else {
+            positional.push(arg.clone());
+        }
+    }
+    Ok((positional, value))
+}
+
+fn parse_pos(value: &str) -> Result<Pos, String> {
+    let parse = |coordinate: &str| coordinate.trim().parse::<i32>().ok();
+    value
+        .split_once(',')
+        .and_then(|(x, y)| Some(Pos::new(parse(x)?, parse(y)?)))
+        .ok_or_else(|| format!("Not a position: {:?}. Expected X,Y.", value))
+}
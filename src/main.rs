@@ -18,25 +18,48 @@
 //     while_true
 // )]
 
+use std::env;
+use std::process;
+
 use bevy::prelude::*;
-use bevy::window::WindowMode;
 use iyes_loopless::prelude::AppLooplessStateExt;
 
 use dsf_core::audio::plugin::DsfAudioPlugin;
+use dsf_core::camera::camera_systems::fit_camera_to_window;
+use dsf_core::config::launch_options::{LaunchOptions, USAGE};
+use dsf_core::config::settings::display_settings::DisplaySettings;
 use dsf_core::config::settings::user_cache::UserCache;
 use dsf_core::level_select::structs::LevelSelectionInstruction;
 use dsf_core::loading::assets::AssetStorage;
 use dsf_core::states::{
-    debug_current_state, AltMenuState, AppState, InGameState, LevelSelectState, LoadingState,
-    MainMenuState, SettingsState,
+    debug_current_state, AltMenuState, AppState, GameOverState, InGameState, LevelSelectState,
+    LoadingState, MainMenuState, SettingsState,
 };
+use dsf_core::systems::checkpoint::CheckpointSave;
+use dsf_core::systems::death::cause::DeathLog;
 use dsf_core::systems::rewind::structs::{CurrentState, Rewind};
 use dsf_core::systems::win_checking::WinCondition;
-use dsf_core::util::window_event_handler::handle_window;
+use dsf_core::util::window_event_handler::{apply_display_mode, handle_window};
 use dsf_editor::level_editor_state::LevelEditorState;
 use dsf_editor::systems::refresh_previews::RefreshPreviewsEvent;
 
 fn main() {
+    let options = match LaunchOptions::parse(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+    let display_settings = DisplaySettings::load_from_file();
+    let window = WindowDescriptor {
+        title: "Dwarf Seeks Fortune".to_string(),
+        ..display_settings.window_descriptor(&options)
+    };
     App::new()
         // Uncomment this to override the default log settings:
         // .insert_resource(bevy::log::LogSettings {
@@ -44,11 +67,7 @@ fn main() {
         //     filter: "wgpu=warn,bevy_ecs=info".to_string(),
         // })
         .insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.1)))
-        .insert_resource(WindowDescriptor {
-            title: "Dwarf Seeks Fortune".to_string(),
-            mode: WindowMode::BorderlessFullscreen,
-            ..default()
-        })
+        .insert_resource(window)
         .add_plugins(DefaultPlugins)
         .add_plugin(DsfAudioPlugin)
         // .add_plugin(LogDiagnosticsPlugin::default())
@@ -61,14 +80,21 @@ fn main() {
         .add_plugin(LevelSelectState)
         .add_plugin(InGameState)
         .add_plugin(SettingsState)
+        .add_plugin(GameOverState)
         .add_plugin(LevelEditorState)
         .add_system(debug_current_state)
+        .add_startup_system(apply_display_mode)
         .add_system(handle_window)
+        .add_system(fit_camera_to_window)
+        .insert_resource(display_settings)
+        .insert_resource(options)
         .init_resource::<AssetStorage>()
         .init_resource::<CurrentState>()
         .init_resource::<Rewind>()
         .init_resource::<LevelSelectionInstruction>()
         .init_resource::<WinCondition>()
+        .init_resource::<DeathLog>()
+        .init_resource::<CheckpointSave>()
         .init_resource::<UserCache>()
         .add_event::<RefreshPreviewsEvent>()
         .run();